@@ -0,0 +1,161 @@
+use crate::events::{EventEmit, UserAction};
+use crate::interfaces::{BeneficiaryAction, MerkleAirdropAction};
+use crate::merkle::{compute_root, hash_leaf};
+use crate::types::{AirdropId, PoolId, SecondTimeStamp};
+use crate::vesting::traits::VestingTokenInfoTrait;
+use crate::vesting::VestingCreateParam;
+use crate::*;
+use near_sdk::json_types::Base58CryptoHash;
+use near_sdk::{CryptoHash, PromiseOrValue};
+
+/// A merkle root over vesting create params, the vestings are materialized lazily
+/// when their beneficiaries claim them for the first time.
+#[derive(BorshSerialize, BorshDeserialize, Debug, Serialize, Deserialize, Clone)]
+#[serde(crate = "near_sdk::serde")]
+pub struct MerkleAirdrop {
+    pub id: AirdropId,
+    pub root: Base58CryptoHash,
+    #[serde(with = "u128_dec_format")]
+    pub total_amount: Balance,
+    #[serde(with = "u128_dec_format")]
+    pub materialized_amount: Balance,
+    #[serde(with = "u64_dec_format")]
+    pub create_time: SecondTimeStamp,
+    // the total amount is allocated from this pool when the airdrop is registered
+    pub pool_id: Option<PoolId>,
+}
+
+#[near_bindgen]
+impl MerkleAirdropAction for TokenVestingContract {
+    #[payable]
+    fn register_merkle_airdrop(
+        &mut self,
+        root: Base58CryptoHash,
+        total_amount: U128,
+        pool_id: Option<PoolId>,
+    ) -> AirdropId {
+        self.assert_role(Role::Creator);
+        self.assert_not_paused();
+        self.assert_pool_given(&pool_id);
+        let prev_storage = env::storage_usage();
+
+        let id = self.internal_assign_id();
+        let airdrop = MerkleAirdrop {
+            id,
+            root,
+            total_amount: total_amount.0,
+            materialized_amount: 0,
+            create_time: get_block_second_time(),
+            pool_id,
+        };
+        if let Some(pool_id) = &airdrop.pool_id {
+            self.internal_allocate_from_pool(pool_id, airdrop.total_amount);
+        }
+        self.airdrops.insert(&id, &airdrop);
+        self.total_unmaterialized_airdrop_amount += airdrop.total_amount;
+        self.internal_check_storage(prev_storage);
+
+        UserAction::RegisterMerkleAirdrop {
+            airdrop_id: &id,
+            root: &airdrop.root,
+            total_amount: &total_amount,
+        }
+        .emit();
+        id
+    }
+
+    fn remove_merkle_airdrop(&mut self, airdrop_id: AirdropId) {
+        self.assert_role(Role::Creator);
+        let airdrop = self
+            .airdrops
+            .remove(&airdrop_id)
+            .unwrap_or_else(|| panic!("No such airdrop id: #{}.", airdrop_id.0));
+        // the amount which is not materialized yet goes back to the pool
+        let unmaterialized_amount = airdrop.total_amount - airdrop.materialized_amount;
+        self.total_unmaterialized_airdrop_amount -= unmaterialized_amount;
+        if let Some(pool_id) = &airdrop.pool_id {
+            self.internal_return_to_pool(pool_id, unmaterialized_amount);
+        }
+
+        UserAction::RemoveMerkleAirdrop {
+            airdrop_id: &airdrop_id,
+        }
+        .emit();
+    }
+
+    #[payable]
+    fn claim_airdrop_vesting(
+        &mut self,
+        airdrop_id: AirdropId,
+        param: VestingCreateParam,
+        proof: Vec<Base58CryptoHash>,
+    ) -> PromiseOrValue<U128> {
+        self.assert_not_paused();
+        let beneficiary = match &param {
+            VestingCreateParam::LinearVesting { beneficiary, .. }
+            | VestingCreateParam::CliffVesting { beneficiary, .. } => beneficiary.clone(),
+        };
+        assert_eq!(
+            env::predecessor_account_id(),
+            beneficiary,
+            "Only the beneficiary can claim an airdrop vesting."
+        );
+        let prev_storage = env::storage_usage();
+
+        let vesting_id = self.internal_materialize_airdrop_vesting(&airdrop_id, param, proof);
+        self.internal_charge_vesting_storage(prev_storage, &[vesting_id]);
+
+        self.claim(vesting_id, None, None)
+    }
+}
+
+impl TokenVestingContract {
+    pub(crate) fn internal_materialize_airdrop_vesting(
+        &mut self,
+        airdrop_id: &AirdropId,
+        param: VestingCreateParam,
+        proof: Vec<Base58CryptoHash>,
+    ) -> VestingId {
+        let mut airdrop = self
+            .airdrops
+            .get(airdrop_id)
+            .unwrap_or_else(|| panic!("No such airdrop id: #{}.", airdrop_id.0));
+
+        let leaf = hash_leaf(&param, env::sha256_array);
+        let proof = proof.into_iter().map(CryptoHash::from).collect_vec();
+        assert_eq!(
+            compute_root(leaf, &proof, env::sha256_array),
+            CryptoHash::from(airdrop.root),
+            "Invalid merkle proof."
+        );
+        assert!(
+            self.materialized_leaves.insert(&(*airdrop_id, leaf)),
+            "The airdrop vesting has been claimed."
+        );
+
+        let vesting_id = self.internal_insert_vesting(param, None);
+        let vesting_amount = self
+            .internal_get_vesting(&vesting_id)
+            .unwrap()
+            .get_vesting_token_info()
+            .total_vesting_amount;
+        airdrop.materialized_amount += vesting_amount;
+        assert!(
+            airdrop.materialized_amount <= airdrop.total_amount,
+            "The airdrop total amount is exceeded."
+        );
+        self.airdrops.insert(airdrop_id, &airdrop);
+        self.total_unmaterialized_airdrop_amount -= vesting_amount;
+        // the amount of the vesting has been allocated when the airdrop was registered
+        if let Some(pool_id) = &airdrop.pool_id {
+            self.vesting_pools.insert(&vesting_id, pool_id);
+        }
+
+        UserAction::MaterializeAirdropVesting {
+            airdrop_id,
+            vesting_id: &vesting_id,
+        }
+        .emit();
+        vesting_id
+    }
+}
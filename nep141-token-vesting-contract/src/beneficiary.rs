@@ -1,6 +1,8 @@
+use crate::constants::MAX_VESTINGS_PER_CLAIM;
 use crate::events::{EventEmit, UserAction, VestingEvent};
 use crate::external::*;
 use crate::interfaces::BeneficiaryAction;
+use crate::types::TransferId;
 use crate::vesting::traits::{Beneficiary, Claimable, Finish};
 use crate::*;
 use crate::{TokenVestingContract, VestingId};
@@ -8,11 +10,22 @@ use near_contract_standards::fungible_token::core::ext_ft_core;
 use near_contract_standards::storage_management::StorageBalance;
 use near_sdk::env::current_account_id;
 use near_sdk::PromiseOrValue;
+use std::collections::BTreeMap;
+
+/// Result of a paginated claim_all, `next_index` is the `from_index` of the next call,
+/// it is `None` when all vestings of the beneficiary have been processed.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(crate = "near_sdk::serde")]
+pub struct ClaimAllResult {
+    pub amount: U128,
+    pub next_index: Option<u32>,
+}
 
 #[near_bindgen]
 impl BeneficiaryAction for TokenVestingContract {
     #[payable]
     fn change_beneficiary(&mut self, vesting_id: VestingId, new_beneficiary: AccountId) {
+        self.assert_not_paused();
         let prev_storage = env::storage_usage();
 
         let mut vesting = self
@@ -34,6 +47,10 @@ impl BeneficiaryAction for TokenVestingContract {
         vesting.set_beneficiary(new_beneficiary);
 
         self.internal_save_vesting(&vesting);
+        self.internal_unindex_vesting(&old_beneficiary, &vesting_id);
+        self.internal_index_vesting(&vesting.get_beneficiary(), &vesting_id);
+        self.internal_reset_claim_policy(&vesting_id);
+        self.auto_distributions.remove(&vesting_id);
 
         self.internal_check_storage(prev_storage);
 
@@ -52,35 +69,161 @@ impl BeneficiaryAction for TokenVestingContract {
         .emit();
     }
 
-    fn claim(&mut self, vesting_id: VestingId) -> PromiseOrValue<U128> {
+    fn claim(
+        &mut self,
+        vesting_id: VestingId,
+        amount: Option<U128>,
+        receiver_id: Option<AccountId>,
+    ) -> PromiseOrValue<U128> {
+        self.assert_not_paused();
         let vesting = self
             .internal_get_vesting(&vesting_id)
             .expect(format!("No such vesting id: #{}.", vesting_id.0).as_str());
+        self.assert_can_claim(&vesting_id, &vesting.get_beneficiary());
+        let receiver_id = self.internal_get_claim_receiver(&vesting.get_beneficiary(), receiver_id);
 
         PromiseOrValue::Promise(
             ext_ft_core::ext(self.token_id.clone())
                 .ft_balance_of(current_account_id())
                 .and(
                     ext_storage_management::ext(self.token_id.clone())
-                        .storage_balance_of(vesting.get_beneficiary()),
+                        .storage_balance_of(receiver_id.clone()),
                 )
-                .then(Self::ext(env::current_account_id()).claim_callback(vesting_id)),
+                .then(Self::ext(env::current_account_id()).claim_callback(
+                    vesting_id,
+                    vesting.get_beneficiary(),
+                    amount,
+                    receiver_id,
+                )),
         )
     }
 
-    fn claim_all(&mut self, beneficiary: Option<AccountId>) -> PromiseOrValue<U128> {
+    fn claim_all(
+        &mut self,
+        beneficiary: Option<AccountId>,
+        from_index: Option<u32>,
+        limit: Option<u32>,
+        amounts: Option<BTreeMap<VestingId, U128>>,
+        receiver_id: Option<AccountId>,
+    ) -> PromiseOrValue<ClaimAllResult> {
+        self.assert_not_paused();
         let beneficiary = beneficiary.unwrap_or(env::predecessor_account_id());
+        let receiver_id = self.internal_get_claim_receiver(&beneficiary, receiver_id);
+        let limit = limit.unwrap_or(MAX_VESTINGS_PER_CLAIM);
+        assert!(
+            limit > 0 && limit <= MAX_VESTINGS_PER_CLAIM,
+            "The limit should be greater than 0 and less or eq than {}.",
+            MAX_VESTINGS_PER_CLAIM
+        );
 
         PromiseOrValue::Promise(
             ext_ft_core::ext(self.token_id.clone())
                 .ft_balance_of(current_account_id())
                 .and(
                     ext_storage_management::ext(self.token_id.clone())
-                        .storage_balance_of(beneficiary.clone()),
+                        .storage_balance_of(receiver_id.clone()),
                 )
-                .then(Self::ext(env::current_account_id()).claim_all_callback(beneficiary)),
+                .then(Self::ext(env::current_account_id()).claim_all_callback(
+                    beneficiary,
+                    from_index.unwrap_or(0),
+                    limit,
+                    amounts.unwrap_or_default(),
+                    receiver_id,
+                    env::predecessor_account_id(),
+                )),
         )
     }
+
+    fn claim_many(
+        &mut self,
+        vesting_ids: Vec<VestingId>,
+        receiver_id: Option<AccountId>,
+    ) -> PromiseOrValue<U128> {
+        self.assert_not_paused();
+        assert!(
+            !vesting_ids.is_empty() && vesting_ids.len() <= MAX_VESTINGS_PER_CLAIM as usize,
+            "The number of vestings should be greater than 0 and less or eq than {}.",
+            MAX_VESTINGS_PER_CLAIM
+        );
+        let beneficiary = self
+            .internal_get_vesting(&vesting_ids[0])
+            .unwrap_or_else(|| panic!("No such vesting id: #{}.", vesting_ids[0].0))
+            .get_beneficiary();
+        self.assert_vestings_of_beneficiary(&beneficiary, &vesting_ids);
+        for vesting_id in &vesting_ids {
+            self.assert_can_claim(vesting_id, &beneficiary);
+        }
+        let receiver_id = self.internal_get_claim_receiver(&beneficiary, receiver_id);
+
+        PromiseOrValue::Promise(
+            ext_ft_core::ext(self.token_id.clone())
+                .ft_balance_of(current_account_id())
+                .and(
+                    ext_storage_management::ext(self.token_id.clone())
+                        .storage_balance_of(receiver_id.clone()),
+                )
+                .then(Self::ext(env::current_account_id()).claim_many_callback(
+                    beneficiary,
+                    vesting_ids,
+                    receiver_id,
+                )),
+        )
+    }
+
+    fn claim_and_call(
+        &mut self,
+        vesting_id: VestingId,
+        receiver_id: AccountId,
+        msg: String,
+    ) -> PromiseOrValue<U128> {
+        self.assert_not_paused();
+        let beneficiary = self
+            .internal_get_vesting(&vesting_id)
+            .unwrap_or_else(|| panic!("No such vesting id: #{}.", vesting_id.0))
+            .get_beneficiary();
+        assert_eq!(
+            env::predecessor_account_id(),
+            beneficiary,
+            "Only the beneficiary can claim and call."
+        );
+
+        PromiseOrValue::Promise(
+            ext_ft_core::ext(self.token_id.clone())
+                .ft_balance_of(current_account_id())
+                .and(
+                    ext_storage_management::ext(self.token_id.clone())
+                        .storage_balance_of(receiver_id.clone()),
+                )
+                .then(
+                    Self::ext(env::current_account_id()).claim_and_call_callback(
+                        vesting_id,
+                        beneficiary,
+                        receiver_id,
+                        msg,
+                    ),
+                ),
+        )
+    }
+
+    fn withdraw_pending_balance(&mut self) -> U128 {
+        self.assert_not_paused();
+        let account_id = env::predecessor_account_id();
+        let amount = self.pending_balances.remove(&account_id).unwrap_or(0);
+        assert!(amount > 0, "No pending balance to withdraw.");
+        self.total_pending_balance -= amount;
+
+        let transfer_id = self.internal_assign_id();
+        UserAction::WithdrawPendingBalance {
+            transfer_id: &transfer_id,
+            account_id: &account_id,
+            amount: &U128(amount),
+        }
+        .emit();
+
+        // the pending balance is credited back if the transfer fails
+        self.internal_send_tokens_or_credit(&account_id, amount, transfer_id);
+        U128(amount)
+    }
 }
 
 #[near_bindgen]
@@ -89,31 +232,165 @@ impl TokenVestingContract {
     pub fn claim_callback(
         &mut self,
         vesting_id: VestingId,
+        beneficiary: AccountId,
+        amount: Option<U128>,
+        receiver_id: AccountId,
         #[callback_unwrap] ft_balance: U128,
         #[callback_unwrap] storage_balance: Option<StorageBalance>,
     ) -> U128 {
+        self.assert_not_paused();
         assert!(
             storage_balance.is_some(),
-            "Failed to claim because the beneficiary hasn't registered in vesting token contract."
+            "Failed to claim because the receiver hasn't registered in vesting token contract."
         );
 
+        let (claimable_amount, transfer_id) = self.internal_claim(
+            &vesting_id,
+            &beneficiary,
+            amount,
+            &receiver_id,
+            ft_balance.0,
+        );
+        self.internal_send_tokens(
+            &receiver_id,
+            &self.token_id.clone(),
+            claimable_amount,
+            transfer_id,
+        );
+        U128(claimable_amount)
+    }
+
+    #[private]
+    pub fn claim_and_call_callback(
+        &mut self,
+        vesting_id: VestingId,
+        beneficiary: AccountId,
+        receiver_id: AccountId,
+        msg: String,
+        #[callback_unwrap] ft_balance: U128,
+        #[callback_unwrap] storage_balance: Option<StorageBalance>,
+    ) -> U128 {
+        self.assert_not_paused();
+        assert!(
+            storage_balance.is_some(),
+            "Failed to claim because the receiver hasn't registered in vesting token contract."
+        );
+
+        let (claimable_amount, transfer_id) =
+            self.internal_claim(&vesting_id, &beneficiary, None, &receiver_id, ft_balance.0);
+        self.internal_send_tokens_call(
+            &receiver_id,
+            &beneficiary,
+            claimable_amount,
+            msg,
+            transfer_id,
+        );
+        U128(claimable_amount)
+    }
+
+    #[private]
+    #[allow(clippy::too_many_arguments)]
+    pub fn claim_all_callback(
+        &mut self,
+        beneficiary: AccountId,
+        from_index: u32,
+        limit: u32,
+        amounts: BTreeMap<VestingId, U128>,
+        receiver_id: AccountId,
+        claimer: AccountId,
+        #[callback_unwrap] ft_balance: U128,
+        #[callback_unwrap] storage_balance: Option<StorageBalance>,
+    ) -> ClaimAllResult {
+        self.assert_not_paused();
+        assert!(
+            storage_balance.is_some(),
+            "Failed to claim because the receiver hasn't registered in vesting token contract."
+        );
+
+        let (vesting_ids, is_last_page) =
+            self.internal_get_beneficiary_page(&beneficiary, from_index, limit);
+        // the vestings which the claimer isn't allowed to claim are skipped
+        let vesting_ids = vesting_ids
+            .into_iter()
+            .filter(|e| self.internal_can_claim(e, &beneficiary, &claimer))
+            .collect_vec();
+        let (amount, removed_count) = self.internal_claim_vestings(
+            &beneficiary,
+            &receiver_id,
+            &vesting_ids,
+            &amounts,
+            ft_balance.0,
+        );
+        ClaimAllResult {
+            amount: U128(amount),
+            next_index: if is_last_page {
+                None
+            } else {
+                Some(from_index + limit - removed_count)
+            },
+        }
+    }
+
+    #[private]
+    pub fn claim_many_callback(
+        &mut self,
+        beneficiary: AccountId,
+        vesting_ids: Vec<VestingId>,
+        receiver_id: AccountId,
+        #[callback_unwrap] ft_balance: U128,
+        #[callback_unwrap] storage_balance: Option<StorageBalance>,
+    ) -> U128 {
+        self.assert_not_paused();
+        assert!(
+            storage_balance.is_some(),
+            "Failed to claim because the receiver hasn't registered in vesting token contract."
+        );
+        // the vestings may have been changed before the callback
+        self.assert_vestings_of_beneficiary(&beneficiary, &vesting_ids);
+
+        let (amount, _) = self.internal_claim_vestings(
+            &beneficiary,
+            &receiver_id,
+            &vesting_ids,
+            &BTreeMap::new(),
+            ft_balance.0,
+        );
+        U128(amount)
+    }
+}
+
+impl TokenVestingContract {
+    /// Claim tokens from the vesting, returns the claimed amount and the id of the transfer.
+    /// The beneficiary is the one seen when the claim was made, the claim fails if the
+    /// beneficiary has been changed since then, as the receiver was chosen for it.
+    fn internal_claim(
+        &mut self,
+        vesting_id: &VestingId,
+        beneficiary: &AccountId,
+        amount: Option<U128>,
+        receiver_id: &AccountId,
+        ft_balance: Balance,
+    ) -> (Balance, TransferId) {
         let mut vesting = self
-            .internal_get_vesting(&vesting_id)
+            .internal_get_vesting(vesting_id)
             .expect(format!("Failed to claim, no such vesting id: #{}", vesting_id.0).as_str());
-        let beneficiary = vesting.get_beneficiary();
-        let claimable_amount = vesting.claim();
+        assert_eq!(
+            &vesting.get_beneficiary(),
+            beneficiary,
+            "The beneficiary of the vesting has been changed before the claim."
+        );
+        let claimable_amount = match amount {
+            Some(amount) => vesting.claim_amount(amount.0),
+            None => vesting.claim(),
+        };
 
         assert!(
-            ft_balance.0 >= claimable_amount,
+            ft_balance >= claimable_amount,
             "Failed to claim because the contract balance is not enough."
         );
 
         if vesting.is_vesting_finish() {
-            self.internal_remove_vesting(&vesting_id);
-            VestingEvent::FinishVesting {
-                vesting_id: &vesting_id,
-            }
-            .emit();
+            self.internal_finish_vesting(&vesting);
         } else {
             self.internal_save_vesting(&vesting);
         }
@@ -123,56 +400,109 @@ impl TokenVestingContract {
 
         UserAction::Claim {
             transfer_id: &transfer_id,
-            vesting_id: &vesting_id,
-            beneficiary: &beneficiary,
+            vesting_id,
+            beneficiary,
+            receiver_id,
             token_id: &self.token_id,
             amount: &U128(claimable_amount),
         }
         .emit();
-
-        self.internal_send_tokens(
-            &beneficiary,
-            &self.token_id.clone(),
-            claimable_amount,
-            transfer_id,
+        self.internal_record_claims(
+            &transfer_id,
+            receiver_id,
+            &[(*vesting_id, claimable_amount)],
         );
-        U128(claimable_amount)
+
+        (claimable_amount, transfer_id)
     }
 
-    #[private]
-    pub fn claim_all_callback(
-        &mut self,
-        beneficiary: AccountId,
-        #[callback_unwrap] ft_balance: U128,
-        #[callback_unwrap] storage_balance: Option<StorageBalance>,
-    ) -> U128 {
-        assert!(
-            storage_balance.is_some(),
-            "Failed to claim because the beneficiary hasn't registered in vesting token contract."
-        );
+    /// Tokens are sent to the beneficiary by default,
+    /// only the beneficiary can direct them to another receiver.
+    fn internal_get_claim_receiver(
+        &self,
+        beneficiary: &AccountId,
+        receiver_id: Option<AccountId>,
+    ) -> AccountId {
+        match receiver_id {
+            Some(receiver_id) if receiver_id.ne(beneficiary) => {
+                assert_eq!(
+                    &env::predecessor_account_id(),
+                    beneficiary,
+                    "Only the beneficiary can claim tokens to another receiver."
+                );
+                receiver_id
+            }
+            _ => beneficiary.clone(),
+        }
+    }
 
-        let mut amount: u128 = 0;
-        let vestings = self
-            .vestings
-            .values()
-            .filter(|e| e.get_beneficiary().eq(&beneficiary))
-            .collect_vec();
+    fn assert_vestings_of_beneficiary(&self, beneficiary: &AccountId, vesting_ids: &[VestingId]) {
+        for vesting_id in vesting_ids {
+            let vesting = self
+                .internal_get_vesting(vesting_id)
+                .unwrap_or_else(|| panic!("No such vesting id: #{}.", vesting_id.0));
+            assert_eq!(
+                &vesting.get_beneficiary(),
+                beneficiary,
+                "All vestings should belong to the same beneficiary."
+            );
+        }
+    }
 
-        let mut claimed_vesting_ids: Vec<VestingId> = vec![];
-        for mut vesting in vestings {
-            let vesting_id = vesting.get_vesting_id();
-            let claimable_amount = vesting.claim();
+    /// Get a page of the vesting ids of the beneficiary, and whether it is the last page.
+    /// Pages are taken from the end of the index, because a finished vesting is swapped with
+    /// the last one when it is removed, in this way the vestings not processed yet never move.
+    pub(crate) fn internal_get_beneficiary_page(
+        &self,
+        beneficiary: &AccountId,
+        from_index: u32,
+        limit: u32,
+    ) -> (Vec<VestingId>, bool) {
+        match self.beneficiary_vestings.get(beneficiary) {
+            Some(vesting_ids) => {
+                let end = vesting_ids.len().saturating_sub(from_index as u64);
+                let start = end.saturating_sub(limit as u64);
+                (
+                    (start..end)
+                        .filter_map(|i| vesting_ids.as_vector().get(i))
+                        .collect_vec(),
+                    start == 0,
+                )
+            }
+            None => (vec![], true),
+        }
+    }
+
+    /// Claim the vestings and send the total amount in one transfer,
+    /// a vesting with a target in amounts claims that amount instead of the whole claimable amount.
+    /// Returns the amount and the number of finished vestings which are removed.
+    fn internal_claim_vestings(
+        &mut self,
+        beneficiary: &AccountId,
+        receiver_id: &AccountId,
+        vesting_ids: &[VestingId],
+        amounts: &BTreeMap<VestingId, U128>,
+        ft_balance: Balance,
+    ) -> (Balance, u32) {
+        let mut amount: u128 = 0;
+        let mut removed_count = 0;
+        let mut claims: Vec<(VestingId, Balance)> = vec![];
+        for vesting_id in vesting_ids {
+            let mut vesting = self
+                .internal_get_vesting(vesting_id)
+                .unwrap_or_else(|| panic!("No such vesting id: #{}.", vesting_id.0));
+            let claimable_amount = match amounts.get(vesting_id) {
+                Some(amount) => vesting.claim_amount(amount.0),
+                None => vesting.claim(),
+            };
 
             if claimable_amount == 0 {
                 continue;
             }
 
             if vesting.is_vesting_finish() {
-                self.internal_remove_vesting(&vesting_id);
-                VestingEvent::FinishVesting {
-                    vesting_id: &vesting_id,
-                }
-                .emit();
+                self.internal_finish_vesting(&vesting);
+                removed_count += 1;
             } else {
                 self.internal_save_vesting(&vesting)
             }
@@ -180,12 +510,12 @@ impl TokenVestingContract {
             VestingEvent::UpdateVesting { vesting: &vesting }.emit();
 
             amount += claimable_amount;
-            claimed_vesting_ids.push(vesting_id);
+            claims.push((*vesting_id, claimable_amount));
         }
 
         if amount > 0 {
             assert!(
-                ft_balance.0 >= amount,
+                ft_balance >= amount,
                 "Failed to claim because the contract balance is not enough."
             );
 
@@ -193,15 +523,17 @@ impl TokenVestingContract {
 
             UserAction::ClaimAll {
                 transfer_id: &transfer_id,
-                vesting_ids: &claimed_vesting_ids,
-                beneficiary: &beneficiary,
+                vesting_ids: &claims.iter().map(|e| e.0).collect_vec(),
+                beneficiary,
+                receiver_id,
                 token_id: &self.token_id.clone(),
                 amount: &U128(amount),
             }
             .emit();
+            self.internal_record_claims(&transfer_id, receiver_id, &claims);
 
-            self.internal_send_tokens(&beneficiary, &self.token_id.clone(), amount, transfer_id);
+            self.internal_send_tokens(receiver_id, &self.token_id.clone(), amount, transfer_id);
         }
-        U128(amount)
+        (amount, removed_count)
     }
 }
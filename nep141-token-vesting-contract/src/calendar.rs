@@ -0,0 +1,214 @@
+use crate::constants::{MAX_CALENDAR_BUCKETS, MAX_VESTINGS_PER_CALENDAR};
+use crate::types::SecondTimeStamp;
+use crate::vesting::traits::{Frozen, VestingAmount};
+use crate::*;
+
+const SECONDS_PER_DAY: u64 = 86_400;
+// 1970-01-01 is a Thursday, weeks start on Monday
+const EPOCH_WEEKDAY_OFFSET: u64 = 3;
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+#[serde(crate = "near_sdk::serde")]
+#[serde(rename_all = "snake_case")]
+pub enum CalendarInterval {
+    Daily,
+    Weekly,
+    Monthly,
+}
+
+/// The amount unlocking in [start_time, end_time).
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(crate = "near_sdk::serde")]
+pub struct CalendarBucket {
+    #[serde(with = "u64_dec_format")]
+    pub start_time: SecondTimeStamp,
+    #[serde(with = "u64_dec_format")]
+    pub end_time: SecondTimeStamp,
+    #[serde(with = "u128_dec_format")]
+    pub amount: Balance,
+}
+
+/// A page of the unlock calendar, the buckets of all pages should be summed up.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(crate = "near_sdk::serde")]
+pub struct UnlockCalendar {
+    pub buckets: Vec<CalendarBucket>,
+    pub next_index: Option<u32>,
+}
+
+/// Days since 1970-01-01 of a date in the proleptic Gregorian calendar.
+fn days_from_civil(year: i64, month: u64, day: u64) -> i64 {
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = year.div_euclid(400);
+    let year_of_era = (year - era * 400) as u64;
+    let day_of_year = (153 * ((month + 9) % 12) + 2) / 5 + day - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    era * 146_097 + day_of_era as i64 - 719_468
+}
+
+/// The year and month of days since 1970-01-01.
+fn civil_from_days(days: i64) -> (i64, u64) {
+    let days = days + 719_468;
+    let era = days.div_euclid(146_097);
+    let day_of_era = (days - era * 146_097) as u64;
+    let year_of_era =
+        (day_of_era - day_of_era / 1460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let month_index = (5 * day_of_year + 2) / 153;
+    let month = if month_index < 10 {
+        month_index + 3
+    } else {
+        month_index - 9
+    };
+    let year = year_of_era as i64 + era * 400 + if month <= 2 { 1 } else { 0 };
+    (year, month)
+}
+
+impl CalendarInterval {
+    /// The start of the next bucket after the time, buckets are aligned to UTC days,
+    /// weeks starting on Monday and calendar months.
+    fn next_bucket_start(&self, time: SecondTimeStamp) -> SecondTimeStamp {
+        match self {
+            CalendarInterval::Daily => (time / SECONDS_PER_DAY + 1) * SECONDS_PER_DAY,
+            CalendarInterval::Weekly => {
+                let week = 7 * SECONDS_PER_DAY;
+                let offset = EPOCH_WEEKDAY_OFFSET * SECONDS_PER_DAY;
+                ((time + offset) / week + 1) * week - offset
+            }
+            CalendarInterval::Monthly => {
+                let (year, month) = civil_from_days((time / SECONDS_PER_DAY) as i64);
+                let (year, month) = if month == 12 {
+                    (year + 1, 1)
+                } else {
+                    (year, month + 1)
+                };
+                days_from_civil(year, month, 1) as u64 * SECONDS_PER_DAY
+            }
+        }
+    }
+
+    pub fn get_buckets(
+        &self,
+        start_time: SecondTimeStamp,
+        end_time: SecondTimeStamp,
+    ) -> Vec<(SecondTimeStamp, SecondTimeStamp)> {
+        assert!(
+            start_time < end_time,
+            "The start time should be less than the end time."
+        );
+        let mut buckets = vec![];
+        let mut bucket_start = start_time;
+        while bucket_start < end_time {
+            assert!(
+                buckets.len() < MAX_CALENDAR_BUCKETS as usize,
+                "Too many buckets, at most {} buckets are returned.",
+                MAX_CALENDAR_BUCKETS
+            );
+            let bucket_end = self.next_bucket_start(bucket_start).min(end_time);
+            buckets.push((bucket_start, bucket_end));
+            bucket_start = bucket_end;
+        }
+        buckets
+    }
+}
+
+impl TokenVestingContract {
+    /// Frozen vestings are skipped, as nothing unlocks for them until they are unfrozen,
+    /// but they still count in the page so that the next page starts at from_index + limit.
+    pub(crate) fn internal_get_unlock_calendar(
+        &self,
+        start_time: SecondTimeStamp,
+        end_time: SecondTimeStamp,
+        interval: CalendarInterval,
+        from_index: u32,
+        limit: u32,
+    ) -> UnlockCalendar {
+        assert!(
+            limit > 0 && limit <= MAX_VESTINGS_PER_CALENDAR,
+            "The limit should be greater than 0 and less or eq than {}.",
+            MAX_VESTINGS_PER_CALENDAR
+        );
+        let mut buckets = interval
+            .get_buckets(start_time, end_time)
+            .into_iter()
+            .map(|(start_time, end_time)| CalendarBucket {
+                start_time,
+                end_time,
+                amount: 0,
+            })
+            .collect_vec();
+
+        let vestings = self
+            .vestings
+            .values()
+            .skip(from_index as usize)
+            .take(limit as usize)
+            .collect_vec();
+        for vesting in vestings.iter().filter(|e| !e.is_frozen()) {
+            // the amount released at a time is unlocked by the end of that second
+            let mut released_amount = vesting.get_released_amount_at(start_time.saturating_sub(1));
+            for bucket in buckets.iter_mut() {
+                let bucket_released_amount = vesting.get_released_amount_at(bucket.end_time - 1);
+                bucket.amount += bucket_released_amount - released_amount;
+                released_amount = bucket_released_amount;
+            }
+        }
+
+        let next_index = from_index as u64 + vestings.len() as u64;
+        UnlockCalendar {
+            buckets,
+            next_index: if next_index < self.vestings.len() {
+                Some(next_index as u32)
+            } else {
+                None
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_civil_days() {
+        assert_eq!(days_from_civil(1970, 1, 1), 0);
+        assert_eq!(days_from_civil(2000, 3, 1), 11_017);
+        assert_eq!(days_from_civil(2024, 2, 29), 19_782);
+        assert_eq!(civil_from_days(0), (1970, 1));
+        assert_eq!(civil_from_days(19_782), (2024, 2));
+        assert_eq!(civil_from_days(19_783), (2024, 3));
+    }
+
+    #[test]
+    fn test_monthly_buckets() {
+        // 2024-01-15 00:00:00 to 2024-03-10 00:00:00
+        let start_time = 1_705_276_800;
+        let end_time = 1_710_028_800;
+        assert_eq!(
+            CalendarInterval::Monthly.get_buckets(start_time, end_time),
+            vec![
+                (start_time, 1_706_745_600),
+                (1_706_745_600, 1_709_251_200),
+                (1_709_251_200, end_time),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_weekly_buckets() {
+        // 2024-01-03 (Wednesday) to 2024-01-16 (Tuesday)
+        let start_time = 1_704_240_000;
+        let end_time = 1_705_363_200;
+        assert_eq!(
+            CalendarInterval::Weekly.get_buckets(start_time, end_time),
+            vec![
+                // Monday 2024-01-08 and 2024-01-15
+                (start_time, 1_704_672_000),
+                (1_704_672_000, 1_705_276_800),
+                (1_705_276_800, end_time),
+            ]
+        );
+        assert_eq!(CalendarInterval::Daily.get_buckets(0, 86_401).len(), 2);
+    }
+}
@@ -0,0 +1,77 @@
+use crate::constants::MAX_CLAIM_RECORDS_PER_VESTING;
+use crate::types::{SecondTimeStamp, TransferId};
+use crate::*;
+
+#[derive(
+    BorshSerialize, BorshDeserialize, Debug, Serialize, Deserialize, Clone, Copy, PartialEq,
+)]
+#[serde(crate = "near_sdk::serde")]
+pub enum TransferStatus {
+    Pending,
+    Succeeded,
+    Failed,
+}
+
+/// A claim of a vesting, several vestings claimed in one transfer share the transfer id.
+#[derive(BorshSerialize, BorshDeserialize, Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(crate = "near_sdk::serde")]
+pub struct ClaimRecord {
+    pub transfer_id: TransferId,
+    #[serde(with = "u128_dec_format")]
+    pub amount: Balance,
+    #[serde(with = "u64_dec_format")]
+    pub claim_time: SecondTimeStamp,
+    pub receiver_id: AccountId,
+    pub status: TransferStatus,
+}
+
+impl TokenVestingContract {
+    /// Record the claims of a transfer, only the latest records of each vesting are kept.
+    /// The log of a removed vesting is only kept if the archive is enabled.
+    pub(crate) fn internal_record_claims(
+        &mut self,
+        transfer_id: &TransferId,
+        receiver_id: &AccountId,
+        claims: &[(VestingId, Balance)],
+    ) {
+        let mut vesting_ids: Vec<VestingId> = vec![];
+        for (vesting_id, amount) in claims {
+            if !self.is_archive_enabled && self.internal_get_vesting(vesting_id).is_none() {
+                continue;
+            }
+            let mut records = self.claim_logs.get(vesting_id).unwrap_or_default();
+            if records.len() >= MAX_CLAIM_RECORDS_PER_VESTING as usize {
+                records.remove(0);
+            }
+            records.push(ClaimRecord {
+                transfer_id: *transfer_id,
+                amount: *amount,
+                claim_time: get_block_second_time(),
+                receiver_id: receiver_id.clone(),
+                status: TransferStatus::Pending,
+            });
+            self.claim_logs.insert(vesting_id, &records);
+            vesting_ids.push(*vesting_id);
+        }
+        if !vesting_ids.is_empty() {
+            self.transfer_claims.insert(transfer_id, &vesting_ids);
+        }
+    }
+
+    /// Update the status of the claim records by the outcome of the transfer.
+    pub(crate) fn internal_resolve_claims(&mut self, transfer_id: &TransferId, is_success: bool) {
+        let status = if is_success {
+            TransferStatus::Succeeded
+        } else {
+            TransferStatus::Failed
+        };
+        for vesting_id in self.transfer_claims.remove(transfer_id).unwrap_or_default() {
+            if let Some(mut records) = self.claim_logs.get(&vesting_id) {
+                if let Some(record) = records.iter_mut().find(|e| e.transfer_id.eq(transfer_id)) {
+                    record.status = status;
+                    self.claim_logs.insert(&vesting_id, &records);
+                }
+            }
+        }
+    }
+}
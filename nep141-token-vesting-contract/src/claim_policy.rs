@@ -0,0 +1,134 @@
+use crate::events::{EventEmit, UserAction};
+use crate::interfaces::ClaimPolicyAction;
+use crate::vesting::traits::Beneficiary;
+use crate::*;
+
+/// Who can trigger a claim of a vesting, the tokens always go to the beneficiary
+/// or a receiver chosen by the beneficiary.
+#[derive(
+    BorshSerialize, BorshDeserialize, Debug, Serialize, Deserialize, Clone, PartialEq, Default,
+)]
+#[serde(crate = "near_sdk::serde")]
+#[serde(rename_all = "snake_case")]
+pub enum ClaimPolicy {
+    /// Any account can claim, e.g. a keeper.
+    #[default]
+    Anyone,
+    BeneficiaryOnly,
+    BeneficiaryAndDelegates,
+}
+
+#[near_bindgen]
+impl ClaimPolicyAction for TokenVestingContract {
+    #[payable]
+    fn set_claim_policy(&mut self, vesting_id: VestingId, policy: ClaimPolicy) {
+        self.assert_vesting_beneficiary(&vesting_id);
+        let prev_storage = env::storage_usage();
+
+        if policy == ClaimPolicy::default() {
+            self.claim_policies.remove(&vesting_id);
+        } else {
+            self.claim_policies.insert(&vesting_id, &policy);
+        }
+        self.internal_check_storage(prev_storage);
+
+        UserAction::SetClaimPolicy {
+            vesting_id: &vesting_id,
+            policy: &policy,
+        }
+        .emit();
+    }
+
+    #[payable]
+    fn add_claim_delegate(&mut self, vesting_id: VestingId, account_id: AccountId) {
+        self.assert_vesting_beneficiary(&vesting_id);
+        let prev_storage = env::storage_usage();
+
+        let mut delegates = self.claim_delegates.get(&vesting_id).unwrap_or_default();
+        assert!(
+            !delegates.contains(&account_id),
+            "{} is already a claim delegate.",
+            account_id
+        );
+        delegates.push(account_id.clone());
+        self.claim_delegates.insert(&vesting_id, &delegates);
+        self.internal_check_storage(prev_storage);
+
+        UserAction::AddClaimDelegate {
+            vesting_id: &vesting_id,
+            account_id: &account_id,
+        }
+        .emit();
+    }
+
+    fn remove_claim_delegate(&mut self, vesting_id: VestingId, account_id: AccountId) {
+        self.assert_vesting_beneficiary(&vesting_id);
+
+        let mut delegates = self.claim_delegates.get(&vesting_id).unwrap_or_default();
+        assert!(
+            delegates.contains(&account_id),
+            "{} is not a claim delegate.",
+            account_id
+        );
+        delegates.retain(|e| e.ne(&account_id));
+        if delegates.is_empty() {
+            self.claim_delegates.remove(&vesting_id);
+        } else {
+            self.claim_delegates.insert(&vesting_id, &delegates);
+        }
+
+        UserAction::RemoveClaimDelegate {
+            vesting_id: &vesting_id,
+            account_id: &account_id,
+        }
+        .emit();
+    }
+}
+
+impl TokenVestingContract {
+    fn assert_vesting_beneficiary(&self, vesting_id: &VestingId) {
+        let vesting = self
+            .internal_get_vesting(vesting_id)
+            .unwrap_or_else(|| panic!("No such vesting id: #{}.", vesting_id.0));
+        assert_eq!(
+            env::predecessor_account_id(),
+            vesting.get_beneficiary(),
+            "Only the beneficiary can change the claim policy."
+        );
+    }
+
+    pub(crate) fn internal_can_claim(
+        &self,
+        vesting_id: &VestingId,
+        beneficiary: &AccountId,
+        claimer: &AccountId,
+    ) -> bool {
+        if claimer.eq(beneficiary) {
+            return true;
+        }
+        match self.claim_policies.get(vesting_id).unwrap_or_default() {
+            ClaimPolicy::Anyone => true,
+            ClaimPolicy::BeneficiaryOnly => false,
+            ClaimPolicy::BeneficiaryAndDelegates => self
+                .claim_delegates
+                .get(vesting_id)
+                .map_or(false, |delegates| delegates.contains(claimer)),
+        }
+    }
+
+    pub(crate) fn assert_can_claim(&self, vesting_id: &VestingId, beneficiary: &AccountId) {
+        assert!(
+            self.internal_can_claim(vesting_id, beneficiary, &env::predecessor_account_id()),
+            "The claim policy of vesting #{} doesn't allow {} to claim.",
+            vesting_id.0,
+            env::predecessor_account_id()
+        );
+    }
+
+    /// The policy and delegates are chosen by the beneficiary, they are reset
+    /// when the vesting is removed or gets a new beneficiary.
+    pub(crate) fn internal_reset_claim_policy(&mut self, vesting_id: &VestingId) {
+        self.claim_policies.remove(vesting_id);
+        self.claim_delegates.remove(vesting_id);
+    }
+}
@@ -1,2 +1,21 @@
 pub const T_GAS_FOR_FT_TRANSFER: u64 = 10;
 pub const T_GAS_FOR_RESOLVE_TRANSFER: u64 = 20;
+pub const TEMPLATE_RATIO_DENOMINATOR: u32 = 10_000;
+// max number of vestings processed by a claim_all or claim_many call
+pub const MAX_VESTINGS_PER_CLAIM: u32 = 50;
+pub const T_GAS_FOR_FT_TRANSFER_CALL: u64 = 50;
+// max number of opted in vestings processed by a distribute call, each beneficiary needs a transfer
+pub const MAX_VESTINGS_PER_DISTRIBUTE: u32 = 5;
+// storage reserved for an account registered by storage_deposit
+pub const STORAGE_ACCOUNT_BYTES: u64 = 200;
+// only the latest claim records of a vesting are kept
+pub const MAX_CLAIM_RECORDS_PER_VESTING: u32 = 20;
+// max number of points returned by get_unlock_timeline
+pub const MAX_UNLOCK_TIMELINE_POINTS: u32 = 100;
+pub const MAX_CALENDAR_BUCKETS: u32 = 400;
+// max number of vestings aggregated by a get_unlock_calendar call
+pub const MAX_VESTINGS_PER_CALENDAR: u32 = 100;
+
+pub const MAX_VESTINGS_PER_VIEW: u32 = 100;
+// the pool utilization is in basis points of the cap
+pub const POOL_UTILIZATION_DENOMINATOR: u32 = 10_000;
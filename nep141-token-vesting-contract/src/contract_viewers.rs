@@ -1,6 +1,28 @@
+use crate::airdrop::MerkleAirdrop;
+use crate::calendar::{CalendarInterval, UnlockCalendar};
+use crate::claim_history::ClaimRecord;
+use crate::claim_policy::ClaimPolicy;
+use crate::constants::{MAX_VESTINGS_PER_CALENDAR, MAX_VESTINGS_PER_VIEW};
+use crate::distribution::{AutoDistribution, DistributionFloors, DistributionTip};
 use crate::interfaces::Viewer;
-use crate::vesting::traits::{Beneficiary, VestingAmount};
+use crate::merkle::hash_leaf;
+use crate::multisig::Proposal;
+use crate::pool::VestingPoolView;
+use crate::roles::Role;
+use crate::stats::VestingStats;
+use crate::storage::VestingStorage;
+use crate::template::VestingTemplateView;
+use crate::timelock::ScheduledAction;
+use crate::types::{AirdropId, PoolId};
+use crate::vesting::archive::{ArchivedVesting, VestingStatus};
+use crate::vesting::filter::VestingFilter;
+use crate::vesting::metadata::VestingMetadata;
+use crate::vesting::timeline::{UnlockPoint, VestingAmounts};
+use crate::vesting::traits::VestingAmount;
+use crate::vesting::view::VestingView;
+use crate::vesting::VestingCreateParam;
 use crate::*;
+use near_sdk::json_types::U64;
 
 #[near_bindgen]
 impl Viewer for TokenVestingContract {
@@ -13,24 +35,9 @@ impl Viewer for TokenVestingContract {
         from_index: u32,
         limit: u32,
         beneficiary: Option<AccountId>,
+        filter: Option<VestingFilter>,
     ) -> Vec<Vesting> {
-        // let b = beneficiary.un
-        self.vestings
-            .iter()
-            .filter(|e| {
-                if beneficiary.is_none() {
-                    return true;
-                }
-                let beneficiary_in_vesting = match &e.1 {
-                    Vesting::NaturalTimeLinearVesting(linear) => &linear.beneficiary,
-                    Vesting::TimeCliffVesting(cliff) => &cliff.beneficiary,
-                };
-                beneficiary_in_vesting.eq(beneficiary.as_ref().unwrap())
-            })
-            .skip(from_index as usize)
-            .take(limit as usize)
-            .map(|e| e.1)
-            .collect_vec()
+        self.internal_get_vestings(from_index, limit, beneficiary.as_ref(), filter.as_ref())
     }
 
     fn get_vesting_by_id(&self, vesting_id: VestingId) -> Vesting {
@@ -38,6 +45,35 @@ impl Viewer for TokenVestingContract {
             .expect(format!("Failed to get vesting, id: #{}", vesting_id.0).as_str())
     }
 
+    fn get_vesting_view(&self, vesting_id: VestingId) -> Option<VestingView> {
+        self.internal_get_vesting(&vesting_id).map(|e| e.get_view())
+    }
+
+    fn get_vesting_views(
+        &self,
+        from_index: u32,
+        limit: u32,
+        beneficiary: Option<AccountId>,
+        filter: Option<VestingFilter>,
+    ) -> Vec<VestingView> {
+        self.internal_get_vestings(from_index, limit, beneficiary.as_ref(), filter.as_ref())
+            .iter()
+            .map(|e| e.get_view())
+            .collect_vec()
+    }
+
+    fn get_vestings_by_ids(&self, vesting_ids: Vec<VestingId>) -> Vec<Option<Vesting>> {
+        assert!(
+            vesting_ids.len() <= MAX_VESTINGS_PER_VIEW as usize,
+            "Too many vesting ids, at most {} vestings are returned.",
+            MAX_VESTINGS_PER_VIEW
+        );
+        vesting_ids
+            .iter()
+            .map(|e| self.internal_get_vesting(e))
+            .collect_vec()
+    }
+
     fn get_claimable_amount(&self, vesting_id: VestingId) -> U128 {
         self.internal_get_vesting(&vesting_id)
             .expect("No such vesting.")
@@ -46,14 +82,220 @@ impl Viewer for TokenVestingContract {
     }
 
     fn get_all_claimable_amount(&self, beneficiary: Option<AccountId>) -> U128 {
-        U128(
-            self.vestings
-                .values()
-                .filter(|e| {
-                    beneficiary.is_none() || e.get_beneficiary().eq(&beneficiary.as_ref().unwrap())
-                })
-                .map(|e| e.get_claimable_amount())
-                .sum(),
+        let vestings = match beneficiary {
+            Some(beneficiary) => self.internal_get_beneficiary_vestings(&beneficiary),
+            None => self.vestings.values().collect_vec(),
+        };
+        U128(vestings.iter().map(|e| e.get_claimable_amount()).sum())
+    }
+
+    fn get_role_members(&self, role: Role) -> Vec<AccountId> {
+        self.internal_get_role_members(role)
+    }
+
+    fn has_role(&self, role: Role, account_id: AccountId) -> bool {
+        self.internal_has_role(role, &account_id)
+    }
+
+    fn get_timelock_delay(&self) -> U64 {
+        U64(self.timelock_delay)
+    }
+
+    fn get_scheduled_actions(&self, from_index: u32, limit: u32) -> Vec<ScheduledAction> {
+        self.scheduled_actions
+            .values()
+            .skip(from_index as usize)
+            .take(limit as usize)
+            .collect_vec()
+    }
+
+    fn get_approvers(&self) -> Vec<AccountId> {
+        self.approvers.to_vec()
+    }
+
+    fn get_approval_threshold(&self) -> u32 {
+        self.approval_threshold
+    }
+
+    fn get_proposals(&self, from_index: u32, limit: u32) -> Vec<Proposal> {
+        self.proposals
+            .values()
+            .skip(from_index as usize)
+            .take(limit as usize)
+            .collect_vec()
+    }
+
+    fn is_paused(&self) -> bool {
+        self.is_paused
+    }
+
+    fn get_merkle_airdrops(&self, from_index: u32, limit: u32) -> Vec<MerkleAirdrop> {
+        self.airdrops
+            .values()
+            .skip(from_index as usize)
+            .take(limit as usize)
+            .collect_vec()
+    }
+
+    fn is_airdrop_vesting_claimed(&self, airdrop_id: AirdropId, param: VestingCreateParam) -> bool {
+        self.materialized_leaves
+            .contains(&(airdrop_id, hash_leaf(&param, env::sha256_array)))
+    }
+
+    fn get_vesting_templates(&self, from_index: u32, limit: u32) -> Vec<VestingTemplateView> {
+        self.templates
+            .iter()
+            .skip(from_index as usize)
+            .take(limit as usize)
+            .map(|(id, template)| VestingTemplateView { id, template })
+            .collect_vec()
+    }
+
+    fn get_vesting_pools(&self, from_index: u32, limit: u32) -> Vec<VestingPoolView> {
+        self.pools
+            .values()
+            .skip(from_index as usize)
+            .take(limit as usize)
+            .map(|e| e.get_view())
+            .collect_vec()
+    }
+
+    fn get_vesting_pool(&self, pool_id: PoolId) -> Option<VestingPoolView> {
+        self.pools.get(&pool_id).map(|e| e.get_view())
+    }
+
+    fn get_vesting_pool_id(&self, vesting_id: VestingId) -> Option<PoolId> {
+        self.vesting_pools.get(&vesting_id)
+    }
+
+    fn is_pool_required(&self) -> bool {
+        self.is_pool_required
+    }
+
+    fn get_vesting_metadata(&self, vesting_id: VestingId) -> Option<VestingMetadata> {
+        self.vesting_metadata.get(&vesting_id)
+    }
+
+    fn get_vestings_by_tag(&self, tag: String, from_index: u32, limit: u32) -> Vec<Vesting> {
+        self.internal_get_vestings_by_tag(&tag, from_index, limit)
+    }
+
+    fn get_pending_balance(&self, account_id: AccountId) -> U128 {
+        U128(self.pending_balances.get(&account_id).unwrap_or(0))
+    }
+
+    fn get_claim_policy(&self, vesting_id: VestingId) -> ClaimPolicy {
+        self.claim_policies.get(&vesting_id).unwrap_or_default()
+    }
+
+    fn get_claim_delegates(&self, vesting_id: VestingId) -> Vec<AccountId> {
+        self.claim_delegates.get(&vesting_id).unwrap_or_default()
+    }
+
+    fn get_auto_distribution(&self, vesting_id: VestingId) -> Option<AutoDistribution> {
+        self.auto_distributions.get(&vesting_id)
+    }
+
+    fn get_auto_distributions(
+        &self,
+        from_index: u32,
+        limit: u32,
+    ) -> Vec<(VestingId, AutoDistribution)> {
+        self.auto_distributions
+            .iter()
+            .skip(from_index as usize)
+            .take(limit as usize)
+            .collect_vec()
+    }
+
+    fn get_distribution_floors(&self) -> DistributionFloors {
+        DistributionFloors {
+            min_interval: U64(self.min_distribution_interval),
+            min_amount: U128(self.min_distribution_amount),
+        }
+    }
+
+    fn get_distribution_tip(&self) -> DistributionTip {
+        DistributionTip {
+            tip: U128(self.distribution_tip),
+            balance: U128(self.distribution_tip_balance),
+        }
+    }
+
+    fn get_vesting_storage(&self, vesting_id: VestingId) -> Option<VestingStorage> {
+        self.vesting_storage.get(&vesting_id)
+    }
+
+    fn is_archive_enabled(&self) -> bool {
+        self.is_archive_enabled
+    }
+
+    fn get_archived_vesting(&self, vesting_id: VestingId) -> Option<ArchivedVesting> {
+        self.archived_vestings.get(&vesting_id)
+    }
+
+    fn get_archived_vestings(
+        &self,
+        beneficiary: AccountId,
+        from_index: u32,
+        limit: u32,
+    ) -> Vec<ArchivedVesting> {
+        self.internal_get_archived_vestings(&beneficiary, from_index, limit)
+    }
+
+    fn get_vesting_status(&self, vesting_id: VestingId) -> Option<VestingStatus> {
+        self.internal_get_vesting_status(&vesting_id)
+    }
+
+    fn get_claim_history(
+        &self,
+        vesting_id: VestingId,
+        from_index: u32,
+        limit: u32,
+    ) -> Vec<ClaimRecord> {
+        self.claim_logs
+            .get(&vesting_id)
+            .unwrap_or_default()
+            .into_iter()
+            .skip(from_index as usize)
+            .take(limit as usize)
+            .collect_vec()
+    }
+
+    fn get_vesting_amounts_at(&self, vesting_id: VestingId, time: U64) -> VestingAmounts {
+        self.internal_get_vesting(&vesting_id)
+            .unwrap_or_else(|| panic!("No such vesting id: #{}.", vesting_id.0))
+            .get_amounts_at(time.0)
+    }
+
+    fn get_unlock_timeline(
+        &self,
+        vesting_id: VestingId,
+        interval: Option<U64>,
+    ) -> Vec<UnlockPoint> {
+        self.internal_get_vesting(&vesting_id)
+            .unwrap_or_else(|| panic!("No such vesting id: #{}.", vesting_id.0))
+            .get_unlock_timeline(interval.map(|e| e.0))
+    }
+
+    fn get_unlock_calendar(
+        &self,
+        start_time: U64,
+        end_time: U64,
+        interval: CalendarInterval,
+        from_index: Option<u32>,
+        limit: Option<u32>,
+    ) -> UnlockCalendar {
+        self.internal_get_unlock_calendar(
+            start_time.0,
+            end_time.0,
+            interval,
+            from_index.unwrap_or(0),
+            limit.unwrap_or(MAX_VESTINGS_PER_CALENDAR),
         )
     }
+
+    fn get_stats(&self) -> VestingStats {
+        self.stats.clone()
+    }
 }
@@ -0,0 +1,346 @@
+use crate::constants::MAX_VESTINGS_PER_DISTRIBUTE;
+use crate::events::{EventEmit, UserAction, VestingEvent};
+use crate::interfaces::DistributionAction;
+use crate::types::SecondTimeStamp;
+use crate::vesting::traits::{Beneficiary, Claimable, Finish, Frozen, VestingAmount};
+use crate::*;
+use near_contract_standards::fungible_token::core::ext_ft_core;
+use near_contract_standards::upgrade::Ownable;
+use near_sdk::json_types::U64;
+use near_sdk::PromiseOrValue;
+use std::collections::BTreeMap;
+
+/// The settings of a vesting opted in to the automatic distribution.
+#[derive(BorshSerialize, BorshDeserialize, Debug, Serialize, Deserialize, Clone)]
+#[serde(crate = "near_sdk::serde")]
+pub struct AutoDistribution {
+    // min seconds between two distributions of the vesting
+    #[serde(with = "u64_dec_format")]
+    pub min_interval: u64,
+    // the vesting is skipped if its claimable amount is less than this
+    #[serde(with = "u128_dec_format")]
+    pub min_amount: Balance,
+    #[serde(with = "u64_dec_format")]
+    pub last_distribute_time: SecondTimeStamp,
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+#[serde(crate = "near_sdk::serde")]
+pub struct DistributeResult {
+    pub vesting_ids: Vec<VestingId>,
+    pub amount: U128,
+    // NEAR sent to the caller
+    pub tip: U128,
+    pub next_index: Option<u32>,
+}
+
+/// The floors of the min interval and min amount of an opt-in.
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+#[serde(crate = "near_sdk::serde")]
+pub struct DistributionFloors {
+    pub min_interval: U64,
+    pub min_amount: U128,
+}
+
+/// The tip per distributed vesting and the remaining funded balance.
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+#[serde(crate = "near_sdk::serde")]
+pub struct DistributionTip {
+    pub tip: U128,
+    pub balance: U128,
+}
+
+#[near_bindgen]
+impl DistributionAction for TokenVestingContract {
+    #[payable]
+    fn enable_auto_distribution(
+        &mut self,
+        vesting_id: VestingId,
+        min_interval: U64,
+        min_amount: U128,
+    ) {
+        let vesting = self
+            .internal_get_vesting(&vesting_id)
+            .unwrap_or_else(|| panic!("No such vesting id: #{}.", vesting_id.0));
+        assert_eq!(
+            env::predecessor_account_id(),
+            vesting.get_beneficiary(),
+            "Only the beneficiary can change the auto distribution."
+        );
+        assert!(
+            min_interval.0 >= self.min_distribution_interval,
+            "The min interval should be greater or eq than {}.",
+            self.min_distribution_interval
+        );
+        assert!(
+            min_amount.0 >= self.min_distribution_amount,
+            "The min amount should be greater or eq than {}.",
+            self.min_distribution_amount
+        );
+        let prev_storage = env::storage_usage();
+
+        let last_distribute_time = self
+            .auto_distributions
+            .get(&vesting_id)
+            .map(|e| e.last_distribute_time)
+            .unwrap_or(0);
+        self.auto_distributions.insert(
+            &vesting_id,
+            &AutoDistribution {
+                min_interval: min_interval.0,
+                min_amount: min_amount.0,
+                last_distribute_time,
+            },
+        );
+        self.internal_check_storage(prev_storage);
+
+        UserAction::EnableAutoDistribution {
+            vesting_id: &vesting_id,
+            min_interval: &min_interval,
+            min_amount: &min_amount,
+        }
+        .emit();
+    }
+
+    fn disable_auto_distribution(&mut self, vesting_id: VestingId) {
+        let vesting = self
+            .internal_get_vesting(&vesting_id)
+            .unwrap_or_else(|| panic!("No such vesting id: #{}.", vesting_id.0));
+        assert_eq!(
+            env::predecessor_account_id(),
+            vesting.get_beneficiary(),
+            "Only the beneficiary can change the auto distribution."
+        );
+        assert!(
+            self.auto_distributions.remove(&vesting_id).is_some(),
+            "The vesting #{} isn't opted in to the auto distribution.",
+            vesting_id.0
+        );
+
+        UserAction::DisableAutoDistribution {
+            vesting_id: &vesting_id,
+        }
+        .emit();
+    }
+
+    fn set_distribution_tip(&mut self, tip: U128) {
+        self.assert_role(Role::Admin);
+        if tip.0 > 0 {
+            self.assert_distribution_floors_set();
+        }
+        self.distribution_tip = tip.0;
+
+        UserAction::SetDistributionTip { tip: &tip }.emit();
+    }
+
+    fn set_distribution_floors(&mut self, min_interval: U64, min_amount: U128) {
+        self.assert_owner();
+        assert!(
+            self.distribution_tip == 0 || (min_interval.0 > 0 && min_amount.0 > 0),
+            "The distribution floors should be greater than 0 while a tip is set."
+        );
+        self.min_distribution_interval = min_interval.0;
+        self.min_distribution_amount = min_amount.0;
+
+        UserAction::SetDistributionFloors {
+            min_interval: &min_interval,
+            min_amount: &min_amount,
+        }
+        .emit();
+    }
+
+    #[payable]
+    fn fund_distribution_tips(&mut self) {
+        self.assert_role(Role::Admin);
+        self.assert_distribution_floors_set();
+        let amount = env::attached_deposit();
+        assert!(amount > 0, "The attached deposit should be greater than 0.");
+        self.distribution_tip_balance += amount;
+
+        UserAction::FundDistributionTips {
+            account_id: &env::predecessor_account_id(),
+            amount: &U128(amount),
+        }
+        .emit();
+    }
+
+    fn withdraw_distribution_tips(&mut self, amount: Option<U128>) -> U128 {
+        self.assert_owner();
+        let amount = amount.map_or(self.distribution_tip_balance, |e| e.0);
+        assert!(
+            amount > 0 && amount <= self.distribution_tip_balance,
+            "The amount should be greater than 0 and less or eq than the tip balance {}.",
+            self.distribution_tip_balance
+        );
+        self.distribution_tip_balance -= amount;
+        Promise::new(env::predecessor_account_id()).transfer(amount);
+
+        UserAction::WithdrawDistributionTips {
+            account_id: &env::predecessor_account_id(),
+            amount: &U128(amount),
+        }
+        .emit();
+        U128(amount)
+    }
+
+    fn distribute(
+        &mut self,
+        from_index: Option<u32>,
+        limit: Option<u32>,
+    ) -> PromiseOrValue<DistributeResult> {
+        self.assert_not_paused();
+        let limit = limit.unwrap_or(MAX_VESTINGS_PER_DISTRIBUTE);
+        assert!(
+            limit > 0 && limit <= MAX_VESTINGS_PER_DISTRIBUTE,
+            "The limit should be greater than 0 and less or eq than {}.",
+            MAX_VESTINGS_PER_DISTRIBUTE
+        );
+
+        PromiseOrValue::Promise(
+            ext_ft_core::ext(self.token_id.clone())
+                .ft_balance_of(env::current_account_id())
+                .then(Self::ext(env::current_account_id()).distribute_callback(
+                    env::predecessor_account_id(),
+                    from_index.unwrap_or(0),
+                    limit,
+                )),
+        )
+    }
+}
+
+#[near_bindgen]
+impl TokenVestingContract {
+    #[private]
+    pub fn distribute_callback(
+        &mut self,
+        caller: AccountId,
+        from_index: u32,
+        limit: u32,
+        #[callback_unwrap] ft_balance: U128,
+    ) -> DistributeResult {
+        self.assert_not_paused();
+        let (vesting_ids, is_last_page) = self.internal_get_distribution_page(from_index, limit);
+
+        let now = get_block_second_time();
+        let mut remaining_balance = ft_balance.0;
+        let mut removed_count = 0;
+        let mut distributed_vesting_ids: Vec<VestingId> = vec![];
+        // vestings opted in before the floors were raised are distributed without a tip
+        let mut tipped_count: Balance = 0;
+        // the claimed amount is sent to each beneficiary in one transfer
+        let mut beneficiary_claims: BTreeMap<AccountId, Vec<(VestingId, Balance)>> =
+            BTreeMap::new();
+        for vesting_id in vesting_ids {
+            let (mut vesting, mut auto_distribution) = match (
+                self.internal_get_vesting(&vesting_id),
+                self.auto_distributions.get(&vesting_id),
+            ) {
+                (Some(vesting), Some(auto_distribution)) => (vesting, auto_distribution),
+                _ => continue,
+            };
+            let claimable_amount = vesting.get_claimable_amount();
+            if vesting.is_frozen()
+                || claimable_amount == 0
+                || claimable_amount < auto_distribution.min_amount
+                || claimable_amount > remaining_balance
+                || now < auto_distribution.last_distribute_time + auto_distribution.min_interval
+            {
+                continue;
+            }
+
+            vesting.claim();
+            remaining_balance -= claimable_amount;
+            if vesting.is_vesting_finish() {
+                self.internal_finish_vesting(&vesting);
+                removed_count += 1;
+            } else {
+                self.internal_save_vesting(&vesting);
+                auto_distribution.last_distribute_time = now;
+                self.auto_distributions
+                    .insert(&vesting_id, &auto_distribution);
+            }
+            VestingEvent::UpdateVesting { vesting: &vesting }.emit();
+
+            beneficiary_claims
+                .entry(vesting.get_beneficiary())
+                .or_default()
+                .push((vesting_id, claimable_amount));
+            distributed_vesting_ids.push(vesting_id);
+            if auto_distribution.min_interval >= self.min_distribution_interval
+                && auto_distribution.min_amount >= self.min_distribution_amount
+            {
+                tipped_count += 1;
+            }
+        }
+
+        let mut amount: Balance = 0;
+        for (beneficiary, claims) in beneficiary_claims {
+            let beneficiary_amount: Balance = claims.iter().map(|e| e.1).sum();
+            let transfer_id = self.internal_assign_id();
+            UserAction::ClaimAll {
+                transfer_id: &transfer_id,
+                vesting_ids: &claims.iter().map(|e| e.0).collect_vec(),
+                beneficiary: &beneficiary,
+                receiver_id: &beneficiary,
+                token_id: &self.token_id.clone(),
+                amount: &U128(beneficiary_amount),
+            }
+            .emit();
+            self.internal_record_claims(&transfer_id, &beneficiary, &claims);
+            self.internal_send_tokens_or_credit(&beneficiary, beneficiary_amount, transfer_id);
+            amount += beneficiary_amount;
+        }
+
+        let tip = (self.distribution_tip * tipped_count).min(self.distribution_tip_balance);
+        if tip > 0 {
+            self.distribution_tip_balance -= tip;
+            Promise::new(caller.clone()).transfer(tip);
+        }
+        UserAction::Distribute {
+            caller: &caller,
+            vesting_ids: &distributed_vesting_ids,
+            amount: &U128(amount),
+            tip: &U128(tip),
+        }
+        .emit();
+
+        DistributeResult {
+            vesting_ids: distributed_vesting_ids,
+            amount: U128(amount),
+            tip: U128(tip),
+            next_index: if is_last_page {
+                None
+            } else {
+                Some(from_index + limit - removed_count)
+            },
+        }
+    }
+}
+
+impl TokenVestingContract {
+    /// Tips are only paid with non-zero floors, otherwise a keeper could drain them
+    /// by distributing dust.
+    fn assert_distribution_floors_set(&self) {
+        assert!(
+            self.min_distribution_interval > 0 && self.min_distribution_amount > 0,
+            "The distribution floors should be set greater than 0 before the tips."
+        );
+    }
+
+    /// Get a page of the opted in vesting ids, and whether it is the last page.
+    /// Like the claim page, it is taken from the end, because a finished vesting
+    /// is swapped with the last one when it is removed.
+    fn internal_get_distribution_page(
+        &self,
+        from_index: u32,
+        limit: u32,
+    ) -> (Vec<VestingId>, bool) {
+        let keys = self.auto_distributions.keys_as_vector();
+        let end = keys.len().saturating_sub(from_index as u64);
+        let start = end.saturating_sub(limit as u64);
+        (
+            (start..end).filter_map(|i| keys.get(i)).collect_vec(),
+            start == 0,
+        )
+    }
+}
@@ -1,6 +1,11 @@
-use crate::types::TransferId;
+use crate::claim_policy::ClaimPolicy;
+use crate::roles::Role;
+use crate::template::VestingTemplate;
+use crate::timelock::SensitiveAction;
+use crate::types::{AirdropId, PoolId, ProposalId, ScheduledActionId, TemplateId, TransferId};
+use crate::vesting::metadata::VestingMetadata;
 use crate::{Vesting, VestingId};
-use near_sdk::json_types::U128;
+use near_sdk::json_types::{Base58CryptoHash, U128, U64};
 use near_sdk::serde::Serialize;
 use near_sdk::serde_json::{json, Value};
 use near_sdk::{log, AccountId};
@@ -16,6 +21,7 @@ pub enum VestingEvent<'a> {
     CreateVesting {
         vesting: &'a Vesting,
         token_id: &'a AccountId,
+        metadata: &'a Option<VestingMetadata>,
     },
     UpdateVesting {
         vesting: &'a Vesting,
@@ -45,6 +51,15 @@ pub enum UserAction<'a> {
     TerminateVesting {
         vesting_id: &'a VestingId,
     },
+    BulkFreezeVesting {
+        vesting_ids: &'a Vec<VestingId>,
+    },
+    BulkUnfreezeVesting {
+        vesting_ids: &'a Vec<VestingId>,
+    },
+    BulkTerminateVesting {
+        vesting_ids: &'a Vec<VestingId>,
+    },
     ChangeBeneficiary {
         vesting_id: &'a VestingId,
         old_beneficiary: &'a AccountId,
@@ -54,6 +69,7 @@ pub enum UserAction<'a> {
         transfer_id: &'a TransferId,
         vesting_id: &'a VestingId,
         beneficiary: &'a AccountId,
+        receiver_id: &'a AccountId,
         token_id: &'a AccountId,
         amount: &'a U128,
     },
@@ -61,6 +77,7 @@ pub enum UserAction<'a> {
         transfer_id: &'a TransferId,
         vesting_ids: &'a Vec<VestingId>,
         beneficiary: &'a AccountId,
+        receiver_id: &'a AccountId,
         token_id: &'a AccountId,
         amount: &'a U128,
     },
@@ -69,6 +86,171 @@ pub enum UserAction<'a> {
         token_id: &'a AccountId,
         amount: &'a U128,
     },
+    CreditPendingBalance {
+        transfer_id: &'a TransferId,
+        account_id: &'a AccountId,
+        amount: &'a U128,
+    },
+    WithdrawPendingBalance {
+        transfer_id: &'a TransferId,
+        account_id: &'a AccountId,
+        amount: &'a U128,
+    },
+    SetClaimPolicy {
+        vesting_id: &'a VestingId,
+        policy: &'a ClaimPolicy,
+    },
+    AddClaimDelegate {
+        vesting_id: &'a VestingId,
+        account_id: &'a AccountId,
+    },
+    RemoveClaimDelegate {
+        vesting_id: &'a VestingId,
+        account_id: &'a AccountId,
+    },
+    EnableAutoDistribution {
+        vesting_id: &'a VestingId,
+        min_interval: &'a U64,
+        min_amount: &'a U128,
+    },
+    DisableAutoDistribution {
+        vesting_id: &'a VestingId,
+    },
+    SetDistributionTip {
+        tip: &'a U128,
+    },
+    SetDistributionFloors {
+        min_interval: &'a U64,
+        min_amount: &'a U128,
+    },
+    FundDistributionTips {
+        account_id: &'a AccountId,
+        amount: &'a U128,
+    },
+    WithdrawDistributionTips {
+        account_id: &'a AccountId,
+        amount: &'a U128,
+    },
+    Distribute {
+        caller: &'a AccountId,
+        vesting_ids: &'a Vec<VestingId>,
+        amount: &'a U128,
+        tip: &'a U128,
+    },
+    StorageDeposit {
+        account_id: &'a AccountId,
+        amount: &'a U128,
+    },
+    StorageWithdraw {
+        account_id: &'a AccountId,
+        amount: &'a U128,
+    },
+    RefundVestingStorage {
+        vesting_id: &'a VestingId,
+        account_id: &'a AccountId,
+        amount: &'a U128,
+    },
+    GrantRole {
+        role: &'a Role,
+        account_id: &'a AccountId,
+    },
+    RevokeRole {
+        role: &'a Role,
+        account_id: &'a AccountId,
+    },
+    AmendVesting {
+        vesting_id: &'a VestingId,
+    },
+    Withdraw {
+        transfer_id: &'a TransferId,
+        receiver_id: &'a AccountId,
+        token_id: &'a AccountId,
+        amount: &'a U128,
+    },
+    ScheduleAction {
+        action_id: &'a ScheduledActionId,
+        action: &'a SensitiveAction,
+        executable_time: &'a U64,
+    },
+    CancelScheduledAction {
+        action_id: &'a ScheduledActionId,
+    },
+    ExecuteScheduledAction {
+        action_id: &'a ScheduledActionId,
+    },
+    SetApprovers {
+        approvers: &'a Vec<AccountId>,
+        threshold: &'a u32,
+    },
+    ProposeAction {
+        proposal_id: &'a ProposalId,
+        action: &'a SensitiveAction,
+    },
+    ApproveProposal {
+        proposal_id: &'a ProposalId,
+        approver: &'a AccountId,
+    },
+    CancelProposal {
+        proposal_id: &'a ProposalId,
+    },
+    ExecuteProposal {
+        proposal_id: &'a ProposalId,
+    },
+    PauseContract {
+        account_id: &'a AccountId,
+    },
+    ResumeContract {
+        account_id: &'a AccountId,
+    },
+    SetArchiveEnabled {
+        enabled: &'a bool,
+    },
+    RegisterMerkleAirdrop {
+        airdrop_id: &'a AirdropId,
+        root: &'a Base58CryptoHash,
+        total_amount: &'a U128,
+    },
+    RemoveMerkleAirdrop {
+        airdrop_id: &'a AirdropId,
+    },
+    MaterializeAirdropVesting {
+        airdrop_id: &'a AirdropId,
+        vesting_id: &'a VestingId,
+    },
+    SetVestingTemplate {
+        template_id: &'a TemplateId,
+        template: &'a VestingTemplate,
+    },
+    RemoveVestingTemplate {
+        template_id: &'a TemplateId,
+    },
+    CreateVestingFromTemplate {
+        template_id: &'a TemplateId,
+        vesting_id: &'a VestingId,
+    },
+    CreateVestingPool {
+        pool_id: &'a PoolId,
+        cap: &'a U128,
+    },
+    SetVestingPoolCap {
+        pool_id: &'a PoolId,
+        cap: &'a U128,
+    },
+    RemoveVestingPool {
+        pool_id: &'a PoolId,
+    },
+    SetPoolRequired {
+        required: &'a bool,
+    },
+    SetVestingMetadata {
+        vesting_id: &'a VestingId,
+        metadata: &'a Option<VestingMetadata>,
+    },
+    FundVestingPool {
+        pool_id: &'a PoolId,
+        sender_id: &'a AccountId,
+        amount: &'a U128,
+    },
 }
 
 #[derive(Serialize, Debug, Clone)]
@@ -81,6 +263,10 @@ pub enum ActionStatus<'a> {
         transfer_id: &'a TransferId,
         is_success: &'a bool,
     },
+    FtTransferCallResult {
+        transfer_id: &'a TransferId,
+        used_amount: &'a U128,
+    },
 }
 
 pub trait EventEmit {
@@ -144,6 +330,11 @@ mod tests {
                 create_time: get_block_second_time(),
             })),
             token_id: &usdt(),
+            metadata: &Some(VestingMetadata {
+                title: Some("Seed grant".to_string()),
+                tag: Some("team".to_string()),
+                ..Default::default()
+            }),
         }
         .emit();
     }
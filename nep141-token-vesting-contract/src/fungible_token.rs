@@ -1,4 +1,6 @@
-use crate::constants::{T_GAS_FOR_FT_TRANSFER, T_GAS_FOR_RESOLVE_TRANSFER};
+use crate::constants::{
+    T_GAS_FOR_FT_TRANSFER, T_GAS_FOR_FT_TRANSFER_CALL, T_GAS_FOR_RESOLVE_TRANSFER,
+};
 use crate::events::{ActionStatus, EventEmit};
 use crate::types::TransferId;
 use crate::*;
@@ -33,6 +35,130 @@ impl TokenVestingContract {
             );
     }
 
+    /// Send tokens by ft_transfer_call, the amount not used by the receiver
+    /// is credited to the pending balance of the beneficiary.
+    pub(crate) fn internal_send_tokens_call(
+        &mut self,
+        receiver_id: &AccountId,
+        beneficiary: &AccountId,
+        amount: Balance,
+        msg: String,
+        transfer_id: TransferId,
+    ) {
+        assert!(amount > 0, "Failed to send tokens because amount is 0.");
+        ext_ft_core::ext(self.token_id.clone())
+            .with_attached_deposit(ONE_YOCTO)
+            .with_static_gas(Gas::ONE_TERA.mul(T_GAS_FOR_FT_TRANSFER_CALL))
+            .ft_transfer_call(receiver_id.clone(), U128(amount), None, msg)
+            .then(
+                Self::ext(env::current_account_id())
+                    .with_static_gas(Gas::ONE_TERA.mul(T_GAS_FOR_RESOLVE_TRANSFER))
+                    .ft_transfer_call_resolved(beneficiary.clone(), U128(amount), transfer_id),
+            );
+    }
+
+    #[private]
+    pub fn ft_transfer_call_resolved(
+        &mut self,
+        beneficiary: AccountId,
+        amount: U128,
+        transfer_id: TransferId,
+    ) -> U128 {
+        assert_eq!(
+            env::promise_results_count(),
+            1,
+            "Expect 1 promise result for ft_transfer_call_resolved."
+        );
+        // ft_transfer_call returns the amount used by the receiver,
+        // nothing is transferred if it failed
+        let used_amount = match env::promise_result(0) {
+            PromiseResult::NotReady => unreachable!(),
+            PromiseResult::Successful(value) => near_sdk::serde_json::from_slice::<U128>(&value)
+                .map(|e| e.0.min(amount.0))
+                .unwrap_or(amount.0),
+            PromiseResult::Failed => 0,
+        };
+        ActionStatus::FtTransferCallResult {
+            transfer_id: &transfer_id,
+            used_amount: &U128(used_amount),
+        }
+        .emit();
+        // the claim failed if nothing was used, the unused amount is not lost,
+        // it is credited to the pending balance
+        self.internal_resolve_claims(&transfer_id, used_amount > 0);
+
+        let unused_amount = amount.0 - used_amount;
+        if unused_amount > 0 {
+            self.internal_credit_pending_balance(&beneficiary, unused_amount, &transfer_id);
+        }
+        U128(used_amount)
+    }
+
+    /// Send tokens when nobody is waiting for the result, e.g. distributed tokens or a
+    /// withdrawn pending balance. The amount of a failed transfer is credited to the
+    /// pending balance of the beneficiary.
+    pub(crate) fn internal_send_tokens_or_credit(
+        &mut self,
+        beneficiary: &AccountId,
+        amount: Balance,
+        transfer_id: TransferId,
+    ) {
+        assert!(amount > 0, "Failed to send tokens because amount is 0.");
+        ext_ft_core::ext(self.token_id.clone())
+            .with_attached_deposit(ONE_YOCTO)
+            .with_static_gas(Gas::ONE_TERA.mul(T_GAS_FOR_FT_TRANSFER))
+            .ft_transfer(beneficiary.clone(), U128(amount), None)
+            .then(
+                Self::ext(env::current_account_id())
+                    .with_static_gas(Gas::ONE_TERA.mul(T_GAS_FOR_RESOLVE_TRANSFER))
+                    .ft_transfer_or_credit_resolved(beneficiary.clone(), U128(amount), transfer_id),
+            );
+    }
+
+    #[private]
+    pub fn ft_transfer_or_credit_resolved(
+        &mut self,
+        beneficiary: AccountId,
+        amount: U128,
+        transfer_id: TransferId,
+    ) {
+        assert_eq!(
+            env::promise_results_count(),
+            1,
+            "Expect 1 promise result for ft_transfer_or_credit_resolved."
+        );
+        let is_success = matches!(env::promise_result(0), PromiseResult::Successful(_));
+        ActionStatus::FtTransferResult {
+            transfer_id: &transfer_id,
+            is_success: &is_success,
+        }
+        .emit();
+
+        self.internal_resolve_claims(&transfer_id, is_success);
+        if !is_success {
+            self.internal_credit_pending_balance(&beneficiary, amount.0, &transfer_id);
+        }
+    }
+
+    fn internal_credit_pending_balance(
+        &mut self,
+        account_id: &AccountId,
+        amount: Balance,
+        transfer_id: &TransferId,
+    ) {
+        let pending_balance = self.pending_balances.get(account_id).unwrap_or(0);
+        self.pending_balances
+            .insert(account_id, &(pending_balance + amount));
+        self.total_pending_balance += amount;
+
+        UserAction::CreditPendingBalance {
+            transfer_id,
+            account_id,
+            amount: &U128(amount),
+        }
+        .emit();
+    }
+
     #[private]
     pub fn ft_transfer_resolved(
         &mut self,
@@ -54,12 +180,16 @@ impl TokenVestingContract {
         );
         match env::promise_result(0) {
             PromiseResult::NotReady => unreachable!(),
-            PromiseResult::Successful(_) => ActionStatus::FtTransferResult {
-                transfer_id: &transfer_id,
-                is_success: &true,
+            PromiseResult::Successful(_) => {
+                self.internal_resolve_claims(&transfer_id, true);
+                ActionStatus::FtTransferResult {
+                    transfer_id: &transfer_id,
+                    is_success: &true,
+                }
+                .emit()
             }
-            .emit(),
             PromiseResult::Failed => {
+                self.internal_resolve_claims(&transfer_id, false);
                 ActionStatus::FtTransferResult {
                     transfer_id: &transfer_id,
                     is_success: &false,
@@ -1,7 +1,30 @@
+use crate::airdrop::MerkleAirdrop;
+use crate::beneficiary::ClaimAllResult;
+use crate::calendar::{CalendarInterval, UnlockCalendar};
+use crate::claim_history::ClaimRecord;
+use crate::claim_policy::ClaimPolicy;
+use crate::distribution::{
+    AutoDistribution, DistributeResult, DistributionFloors, DistributionTip,
+};
+use crate::multisig::Proposal;
+use crate::pool::VestingPoolView;
+use crate::roles::Role;
+use crate::stats::VestingStats;
+use crate::storage::VestingStorage;
+use crate::template::{VestingTemplate, VestingTemplateView};
+use crate::timelock::{ScheduledAction, SensitiveAction};
+use crate::types::{AirdropId, PoolId, ProposalId, ScheduledActionId, TemplateId};
+use crate::vesting::archive::{ArchivedVesting, VestingStatus};
 use crate::vesting::cliff::CliffVestingCheckpoint;
+use crate::vesting::filter::VestingFilter;
+use crate::vesting::metadata::VestingMetadata;
+use crate::vesting::timeline::{UnlockPoint, VestingAmounts};
+use crate::vesting::view::VestingView;
+use crate::vesting::{BulkActionResult, VestingCreateParam, VestingSelector};
 use crate::{Vesting, VestingId};
-use near_sdk::json_types::{U128, U64};
+use near_sdk::json_types::{Base58CryptoHash, U128, U64};
 use near_sdk::{AccountId, PromiseOrValue};
+use std::collections::BTreeMap;
 
 pub trait Viewer {
     fn get_vesting_token_id(&self) -> AccountId;
@@ -11,13 +34,123 @@ pub trait Viewer {
         from_index: u32,
         limit: u32,
         beneficiary: Option<AccountId>,
+        filter: Option<VestingFilter>,
     ) -> Vec<Vesting>;
 
     fn get_vesting_by_id(&self, vesting_id: VestingId) -> Vesting;
 
+    fn get_vesting_view(&self, vesting_id: VestingId) -> Option<VestingView>;
+
+    fn get_vesting_views(
+        &self,
+        from_index: u32,
+        limit: u32,
+        beneficiary: Option<AccountId>,
+        filter: Option<VestingFilter>,
+    ) -> Vec<VestingView>;
+
+    fn get_vestings_by_ids(&self, vesting_ids: Vec<VestingId>) -> Vec<Option<Vesting>>;
+
     fn get_claimable_amount(&self, vesting_id: VestingId) -> U128;
 
     fn get_all_claimable_amount(&self, beneficiary: Option<AccountId>) -> U128;
+
+    fn get_role_members(&self, role: Role) -> Vec<AccountId>;
+
+    fn has_role(&self, role: Role, account_id: AccountId) -> bool;
+
+    fn get_timelock_delay(&self) -> U64;
+
+    fn get_scheduled_actions(&self, from_index: u32, limit: u32) -> Vec<ScheduledAction>;
+
+    fn get_approvers(&self) -> Vec<AccountId>;
+
+    fn get_approval_threshold(&self) -> u32;
+
+    fn get_proposals(&self, from_index: u32, limit: u32) -> Vec<Proposal>;
+
+    fn is_paused(&self) -> bool;
+
+    fn get_merkle_airdrops(&self, from_index: u32, limit: u32) -> Vec<MerkleAirdrop>;
+
+    fn is_airdrop_vesting_claimed(&self, airdrop_id: AirdropId, param: VestingCreateParam) -> bool;
+
+    fn get_vesting_templates(&self, from_index: u32, limit: u32) -> Vec<VestingTemplateView>;
+
+    fn get_vesting_pools(&self, from_index: u32, limit: u32) -> Vec<VestingPoolView>;
+
+    fn get_vesting_pool(&self, pool_id: PoolId) -> Option<VestingPoolView>;
+
+    fn get_vesting_pool_id(&self, vesting_id: VestingId) -> Option<PoolId>;
+
+    fn is_pool_required(&self) -> bool;
+
+    fn get_vesting_metadata(&self, vesting_id: VestingId) -> Option<VestingMetadata>;
+
+    fn get_vestings_by_tag(&self, tag: String, from_index: u32, limit: u32) -> Vec<Vesting>;
+
+    fn get_pending_balance(&self, account_id: AccountId) -> U128;
+
+    fn get_claim_policy(&self, vesting_id: VestingId) -> ClaimPolicy;
+
+    fn get_claim_delegates(&self, vesting_id: VestingId) -> Vec<AccountId>;
+
+    fn get_auto_distribution(&self, vesting_id: VestingId) -> Option<AutoDistribution>;
+
+    fn get_auto_distributions(
+        &self,
+        from_index: u32,
+        limit: u32,
+    ) -> Vec<(VestingId, AutoDistribution)>;
+
+    fn get_distribution_tip(&self) -> DistributionTip;
+
+    fn get_distribution_floors(&self) -> DistributionFloors;
+
+    fn get_vesting_storage(&self, vesting_id: VestingId) -> Option<VestingStorage>;
+
+    fn is_archive_enabled(&self) -> bool;
+
+    fn get_archived_vesting(&self, vesting_id: VestingId) -> Option<ArchivedVesting>;
+
+    fn get_archived_vestings(
+        &self,
+        beneficiary: AccountId,
+        from_index: u32,
+        limit: u32,
+    ) -> Vec<ArchivedVesting>;
+
+    /// Returns None if the vesting never existed, or it was removed while the archive is disabled.
+    fn get_vesting_status(&self, vesting_id: VestingId) -> Option<VestingStatus>;
+
+    /// Returns the released, unreleased and claimable amount of the vesting at the time.
+    fn get_vesting_amounts_at(&self, vesting_id: VestingId, time: U64) -> VestingAmounts;
+
+    /// Returns the total released amount at each unlock time of the vesting,
+    /// interval (in seconds) only applies to linear vestings.
+    fn get_unlock_timeline(&self, vesting_id: VestingId, interval: Option<U64>)
+        -> Vec<UnlockPoint>;
+
+    /// Aggregate the amount unlocking in each bucket between start_time and end_time,
+    /// over a page of all vestings.
+    fn get_unlock_calendar(
+        &self,
+        start_time: U64,
+        end_time: U64,
+        interval: CalendarInterval,
+        from_index: Option<u32>,
+        limit: Option<u32>,
+    ) -> UnlockCalendar;
+
+    /// Returns the latest claims of the vesting, from the oldest to the newest.
+    fn get_claim_history(
+        &self,
+        vesting_id: VestingId,
+        from_index: u32,
+        limit: u32,
+    ) -> Vec<ClaimRecord>;
+
+    fn get_stats(&self) -> VestingStats;
 }
 
 pub trait OwnerAction {
@@ -27,25 +160,194 @@ pub trait OwnerAction {
         start_time: U64,
         end_time: U64,
         total_vesting_amount: U128,
+        pool_id: Option<PoolId>,
+        metadata: Option<VestingMetadata>,
     ) -> VestingId;
 
     fn create_cliff_vesting(
         &mut self,
         beneficiary: AccountId,
         time_cliff_list: Vec<CliffVestingCheckpoint>,
+        pool_id: Option<PoolId>,
+        metadata: Option<VestingMetadata>,
+    ) -> VestingId;
+
+    fn create_vestings(
+        &mut self,
+        params: Vec<VestingCreateParam>,
+        pool_id: Option<PoolId>,
+    ) -> Vec<VestingId>;
+
+    fn set_vesting_template(&mut self, template_id: TemplateId, template: VestingTemplate);
+
+    fn remove_vesting_template(&mut self, template_id: TemplateId);
+
+    fn create_vesting_from_template(
+        &mut self,
+        template_id: TemplateId,
+        beneficiary: AccountId,
+        start_time: U64,
+        amount: U128,
+        pool_id: Option<PoolId>,
+        metadata: Option<VestingMetadata>,
     ) -> VestingId;
 
+    fn set_vesting_metadata(&mut self, vesting_id: VestingId, metadata: Option<VestingMetadata>);
+
     fn freeze_vesting(&mut self, vesting_id: VestingId);
 
     fn unfreeze_vesting(&mut self, vesting_id: VestingId);
 
     fn terminate_vesting(&mut self, vesting_id: VestingId);
+
+    fn bulk_freeze_vesting(
+        &mut self,
+        selector: VestingSelector,
+        from_index: u32,
+        limit: u32,
+    ) -> BulkActionResult;
+
+    fn bulk_unfreeze_vesting(
+        &mut self,
+        selector: VestingSelector,
+        from_index: u32,
+        limit: u32,
+    ) -> BulkActionResult;
+
+    fn bulk_terminate_vesting(
+        &mut self,
+        selector: VestingSelector,
+        from_index: u32,
+        limit: u32,
+    ) -> BulkActionResult;
+
+    fn grant_role(&mut self, role: Role, account_id: AccountId);
+
+    fn revoke_role(&mut self, role: Role, account_id: AccountId);
+
+    fn pause_contract(&mut self);
+
+    fn resume_contract(&mut self);
+
+    /// Keep a summary of the finished and terminated vestings.
+    fn set_archive_enabled(&mut self, enabled: bool);
 }
 
 pub trait BeneficiaryAction {
     fn change_beneficiary(&mut self, vesting_id: VestingId, new_beneficiary: AccountId);
 
-    fn claim(&mut self, vesting_id: VestingId) -> PromiseOrValue<U128>;
+    fn claim(
+        &mut self,
+        vesting_id: VestingId,
+        amount: Option<U128>,
+        receiver_id: Option<AccountId>,
+    ) -> PromiseOrValue<U128>;
+
+    fn claim_all(
+        &mut self,
+        beneficiary: Option<AccountId>,
+        from_index: Option<u32>,
+        limit: Option<u32>,
+        amounts: Option<BTreeMap<VestingId, U128>>,
+        receiver_id: Option<AccountId>,
+    ) -> PromiseOrValue<ClaimAllResult>;
+
+    fn claim_many(
+        &mut self,
+        vesting_ids: Vec<VestingId>,
+        receiver_id: Option<AccountId>,
+    ) -> PromiseOrValue<U128>;
+
+    fn claim_and_call(
+        &mut self,
+        vesting_id: VestingId,
+        receiver_id: AccountId,
+        msg: String,
+    ) -> PromiseOrValue<U128>;
+
+    fn withdraw_pending_balance(&mut self) -> U128;
+}
+
+pub trait Timelock {
+    fn schedule_action(&mut self, action: SensitiveAction) -> ScheduledActionId;
+
+    fn cancel_scheduled_action(&mut self, action_id: ScheduledActionId);
 
-    fn claim_all(&mut self, beneficiary: Option<AccountId>) -> PromiseOrValue<U128>;
+    fn execute_scheduled_action(&mut self, action_id: ScheduledActionId);
+
+    fn set_timelock_delay(&mut self, delay: U64);
+}
+
+pub trait Multisig {
+    fn propose_action(&mut self, action: SensitiveAction) -> ProposalId;
+
+    fn approve_proposal(&mut self, proposal_id: ProposalId);
+
+    fn cancel_proposal(&mut self, proposal_id: ProposalId);
+
+    fn set_approvers(&mut self, approvers: Vec<AccountId>, threshold: u32);
+}
+
+pub trait MerkleAirdropAction {
+    fn register_merkle_airdrop(
+        &mut self,
+        root: Base58CryptoHash,
+        total_amount: U128,
+        pool_id: Option<PoolId>,
+    ) -> AirdropId;
+
+    fn remove_merkle_airdrop(&mut self, airdrop_id: AirdropId);
+
+    fn claim_airdrop_vesting(
+        &mut self,
+        airdrop_id: AirdropId,
+        param: VestingCreateParam,
+        proof: Vec<Base58CryptoHash>,
+    ) -> PromiseOrValue<U128>;
+}
+
+pub trait VestingPoolAction {
+    fn create_vesting_pool(&mut self, pool_id: PoolId, cap: U128);
+
+    fn set_vesting_pool_cap(&mut self, pool_id: PoolId, cap: U128);
+
+    fn remove_vesting_pool(&mut self, pool_id: PoolId);
+
+    /// Reject the vestings and airdrops created without a pool, which is the default.
+    fn set_pool_required(&mut self, required: bool);
+}
+
+pub trait ClaimPolicyAction {
+    fn set_claim_policy(&mut self, vesting_id: VestingId, policy: ClaimPolicy);
+
+    fn add_claim_delegate(&mut self, vesting_id: VestingId, account_id: AccountId);
+
+    fn remove_claim_delegate(&mut self, vesting_id: VestingId, account_id: AccountId);
+}
+
+pub trait DistributionAction {
+    fn enable_auto_distribution(
+        &mut self,
+        vesting_id: VestingId,
+        min_interval: U64,
+        min_amount: U128,
+    );
+
+    fn disable_auto_distribution(&mut self, vesting_id: VestingId);
+
+    fn set_distribution_tip(&mut self, tip: U128);
+
+    /// Set the floors of the min interval and min amount of an opt-in, so that
+    /// the tips can't be drained by distributing dust.
+    fn set_distribution_floors(&mut self, min_interval: U64, min_amount: U128);
+
+    fn fund_distribution_tips(&mut self);
+
+    fn withdraw_distribution_tips(&mut self, amount: Option<U128>) -> U128;
+
+    fn distribute(
+        &mut self,
+        from_index: Option<u32>,
+        limit: Option<u32>,
+    ) -> PromiseOrValue<DistributeResult>;
 }
@@ -1,25 +1,55 @@
+use crate::airdrop::MerkleAirdrop;
+use crate::claim_history::ClaimRecord;
+use crate::claim_policy::ClaimPolicy;
+use crate::distribution::AutoDistribution;
 use crate::events::UserAction;
 use crate::interfaces::OwnerAction;
-use crate::types::VestingId;
+use crate::multisig::Proposal;
+use crate::pool::VestingPool;
+use crate::roles::Role;
+use crate::stats::VestingStats;
+use crate::storage::{StorageAccount, VestingStorage};
+use crate::template::VestingTemplate;
+use crate::timelock::ScheduledAction;
+use crate::types::{
+    AirdropId, PoolId, ProposalId, ScheduledActionId, SecondTimeStamp, TemplateId, TransferId,
+    VestingId,
+};
+use crate::vesting::archive::ArchivedVesting;
+use crate::vesting::metadata::VestingMetadata;
 use crate::vesting::Vesting;
 use itertools::Itertools;
 use near_sdk::borsh::{self, BorshDeserialize, BorshSerialize};
-use near_sdk::collections::UnorderedMap;
+use near_sdk::collections::{LookupMap, LookupSet, UnorderedMap, UnorderedSet, Vector};
 use near_sdk::json_types::U128;
 use near_sdk::serde::{Deserialize, Serialize};
 use near_sdk::{
-    env, log, near_bindgen, AccountId, Balance, BorshStorageKey, PanicOnDefault, Promise,
-    StorageUsage,
+    env, log, near_bindgen, AccountId, Balance, BorshStorageKey, CryptoHash, PanicOnDefault,
+    Promise, StorageUsage,
 };
 
+pub mod airdrop;
 pub mod beneficiary;
+pub mod calendar;
+pub mod claim_history;
+pub mod claim_policy;
 pub mod constants;
 pub mod contract_viewers;
+pub mod distribution;
 pub mod events;
 pub mod external;
 pub mod fungible_token;
 pub mod interfaces;
+pub mod merkle;
+pub mod migration;
+pub mod multisig;
 pub mod owner;
+pub mod pool;
+pub mod roles;
+pub mod stats;
+pub mod storage;
+pub mod template;
+pub mod timelock;
 pub mod types;
 pub mod utils;
 pub mod vesting;
@@ -29,6 +59,33 @@ use crate::utils::*;
 #[derive(BorshStorageKey, BorshSerialize)]
 pub(crate) enum StorageKey {
     Vestings,
+    RoleMembers,
+    RoleMembersInner { role: Role },
+    ScheduledActions,
+    Approvers,
+    Proposals,
+    Airdrops,
+    MaterializedLeaves,
+    Templates,
+    Pools,
+    VestingPools,
+    VestingMetadata,
+    BeneficiaryVestings,
+    BeneficiaryVestingsInner { account_hash: CryptoHash },
+    PendingBalances,
+    ClaimPolicies,
+    ClaimDelegates,
+    AutoDistributions,
+    StorageAccounts,
+    VestingStorage,
+    ArchivedVestings,
+    BeneficiaryArchivedVestings,
+    BeneficiaryArchivedVestingsInner { account_hash: CryptoHash },
+    ClaimLogs,
+    TransferClaims,
+    ReleasedSnapshots,
+    TagVestings,
+    TagVestingsInner { tag_hash: CryptoHash },
 }
 
 #[near_bindgen]
@@ -39,6 +96,60 @@ pub struct TokenVestingContract {
     pub token_id: AccountId,
     pub vestings: UnorderedMap<VestingId, Vesting>,
     pub uuid: u64,
+    pub role_members: LookupMap<Role, UnorderedSet<AccountId>>,
+    // delay in seconds before a scheduled sensitive action can be executed, 0 means disabled
+    pub timelock_delay: SecondTimeStamp,
+    pub scheduled_actions: UnorderedMap<ScheduledActionId, ScheduledAction>,
+    pub approvers: UnorderedSet<AccountId>,
+    // number of approvals needed to execute a proposal, 0 means multisig is disabled
+    pub approval_threshold: u32,
+    pub proposals: UnorderedMap<ProposalId, Proposal>,
+    // contract level pause, blocks claiming, changing beneficiary and creating vestings
+    pub is_paused: bool,
+    pub airdrops: UnorderedMap<AirdropId, MerkleAirdrop>,
+    // (airdrop id, leaf hash) of materialized airdrop vestings
+    pub materialized_leaves: LookupSet<(AirdropId, CryptoHash)>,
+    // the amount of the registered airdrops which is not materialized into vestings yet
+    pub total_unmaterialized_airdrop_amount: Balance,
+    pub templates: UnorderedMap<TemplateId, VestingTemplate>,
+    pub pools: UnorderedMap<PoolId, VestingPool>,
+    // vestings and airdrops can't be created without a pool, unless the owner turns it off
+    pub is_pool_required: bool,
+    // pool of each vesting created against a pool
+    pub vesting_pools: LookupMap<VestingId, PoolId>,
+    pub vesting_metadata: LookupMap<VestingId, VestingMetadata>,
+    // ids of the vestings of each metadata tag
+    pub tag_vestings: LookupMap<String, UnorderedSet<VestingId>>,
+    // ids of the vestings of each beneficiary
+    pub beneficiary_vestings: LookupMap<AccountId, UnorderedSet<VestingId>>,
+    // claimed tokens which were not used by the receiver of a claim_and_call
+    pub pending_balances: LookupMap<AccountId, Balance>,
+    pub total_pending_balance: Balance,
+    // vestings without a policy can be claimed by anyone
+    pub claim_policies: LookupMap<VestingId, ClaimPolicy>,
+    pub claim_delegates: LookupMap<VestingId, Vec<AccountId>>,
+    pub auto_distributions: UnorderedMap<VestingId, AutoDistribution>,
+    // NEAR paid to the caller of distribute for each distributed vesting
+    pub distribution_tip: Balance,
+    // NEAR funded by the owner for the tips
+    pub distribution_tip_balance: Balance,
+    // the min interval and min amount of an opt-in can't be less than these floors
+    pub min_distribution_interval: SecondTimeStamp,
+    pub min_distribution_amount: Balance,
+    // NEP-145 storage deposits
+    pub storage_accounts: LookupMap<AccountId, StorageAccount>,
+    pub vesting_storage: LookupMap<VestingId, VestingStorage>,
+    pub is_archive_enabled: bool,
+    pub archived_vestings: LookupMap<VestingId, ArchivedVesting>,
+    pub beneficiary_archived_vestings: LookupMap<AccountId, Vector<VestingId>>,
+    pub claim_logs: LookupMap<VestingId, Vec<ClaimRecord>>,
+    // the vestings claimed by a transfer which is not resolved yet
+    pub transfer_claims: LookupMap<TransferId, Vec<VestingId>>,
+    pub stats: VestingStats,
+    // the released amount of each vesting counted in the stats
+    pub released_snapshots: LookupMap<VestingId, Balance>,
+    // index of the next vesting to migrate, None if the migration is finished
+    pub migration_cursor: Option<u32>,
 }
 
 #[near_bindgen]
@@ -50,30 +161,87 @@ impl TokenVestingContract {
             token_id,
             vestings: UnorderedMap::new(StorageKey::Vestings),
             uuid: 0,
+            role_members: LookupMap::new(StorageKey::RoleMembers),
+            timelock_delay: 0,
+            scheduled_actions: UnorderedMap::new(StorageKey::ScheduledActions),
+            approvers: UnorderedSet::new(StorageKey::Approvers),
+            approval_threshold: 0,
+            proposals: UnorderedMap::new(StorageKey::Proposals),
+            is_paused: false,
+            airdrops: UnorderedMap::new(StorageKey::Airdrops),
+            materialized_leaves: LookupSet::new(StorageKey::MaterializedLeaves),
+            total_unmaterialized_airdrop_amount: 0,
+            templates: UnorderedMap::new(StorageKey::Templates),
+            pools: UnorderedMap::new(StorageKey::Pools),
+            is_pool_required: true,
+            vesting_pools: LookupMap::new(StorageKey::VestingPools),
+            vesting_metadata: LookupMap::new(StorageKey::VestingMetadata),
+            tag_vestings: LookupMap::new(StorageKey::TagVestings),
+            beneficiary_vestings: LookupMap::new(StorageKey::BeneficiaryVestings),
+            pending_balances: LookupMap::new(StorageKey::PendingBalances),
+            total_pending_balance: 0,
+            claim_policies: LookupMap::new(StorageKey::ClaimPolicies),
+            claim_delegates: LookupMap::new(StorageKey::ClaimDelegates),
+            auto_distributions: UnorderedMap::new(StorageKey::AutoDistributions),
+            distribution_tip: 0,
+            distribution_tip_balance: 0,
+            min_distribution_interval: 0,
+            min_distribution_amount: 0,
+            storage_accounts: LookupMap::new(StorageKey::StorageAccounts),
+            vesting_storage: LookupMap::new(StorageKey::VestingStorage),
+            is_archive_enabled: false,
+            archived_vestings: LookupMap::new(StorageKey::ArchivedVestings),
+            beneficiary_archived_vestings: LookupMap::new(StorageKey::BeneficiaryArchivedVestings),
+            claim_logs: LookupMap::new(StorageKey::ClaimLogs),
+            transfer_claims: LookupMap::new(StorageKey::TransferClaims),
+            stats: VestingStats::default(),
+            released_snapshots: LookupMap::new(StorageKey::ReleasedSnapshots),
+            migration_cursor: None,
         }
     }
 }
 
 impl TokenVestingContract {
+    pub(crate) fn assert_not_paused(&self) {
+        assert!(!self.is_paused, "The contract is paused.");
+        self.assert_migrated();
+    }
+
+    /// The vestings can't be changed until the index and the stats of all vestings are built.
+    pub(crate) fn assert_migrated(&self) {
+        assert!(
+            self.migration_cursor.is_none(),
+            "The vestings are not migrated yet."
+        );
+    }
+
     /// Check how much storage taken costs and refund the left over back.
-    fn internal_check_storage(&self, prev_storage: StorageUsage) {
+    /// If the attached deposit is not enough, the rest is paid by the storage balance
+    /// of the predecessor. Returns the storage cost.
+    fn internal_check_storage(&mut self, prev_storage: StorageUsage) -> Balance {
         let storage_cost = env::storage_usage()
             .checked_sub(prev_storage)
             .unwrap_or_default() as Balance
             * env::storage_byte_cost();
 
         log!("storage cost {}", storage_cost);
-        let refund = env::attached_deposit().checked_sub(storage_cost).expect(
-            format!(
+        match env::attached_deposit().checked_sub(storage_cost) {
+            Some(refund) => {
+                if refund > 0 {
+                    Promise::new(env::predecessor_account_id()).transfer(refund);
+                }
+            }
+            None => assert!(
+                self.internal_use_storage_balance(
+                    &env::predecessor_account_id(),
+                    storage_cost - env::attached_deposit()
+                ),
                 "ERR_STORAGE_DEPOSIT need {}, attached {}",
                 storage_cost,
                 env::attached_deposit()
-            )
-            .as_str(),
-        );
-        if refund > 0 {
-            Promise::new(env::predecessor_account_id()).transfer(refund);
+            ),
         }
+        storage_cost
     }
 }
 
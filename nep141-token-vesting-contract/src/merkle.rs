@@ -0,0 +1,145 @@
+use crate::vesting::VestingCreateParam;
+use near_sdk::borsh::BorshSerialize;
+use near_sdk::CryptoHash;
+
+// prefixes of leaf and node data, so that a node can never be taken as a leaf
+const LEAF_PREFIX: u8 = 0;
+const NODE_PREFIX: u8 = 1;
+
+/// Hash a leaf of the airdrop tree, a leaf is a vesting create param,
+/// which contains the beneficiary, the schedule and the amount.
+pub fn hash_leaf<H: Fn(&[u8]) -> CryptoHash>(param: &VestingCreateParam, hash: H) -> CryptoHash {
+    let mut data = vec![LEAF_PREFIX];
+    data.extend(
+        param
+            .try_to_vec()
+            .expect("Failed to serialize vesting create param."),
+    );
+    hash(&data)
+}
+
+/// Hash two sibling nodes, the pair is sorted so that a proof doesn't need the positions.
+pub fn hash_node<H: Fn(&[u8]) -> CryptoHash>(
+    a: &CryptoHash,
+    b: &CryptoHash,
+    hash: H,
+) -> CryptoHash {
+    let (left, right) = if a <= b { (a, b) } else { (b, a) };
+    let mut data = vec![NODE_PREFIX];
+    data.extend_from_slice(left);
+    data.extend_from_slice(right);
+    hash(&data)
+}
+
+pub fn compute_root<H: Fn(&[u8]) -> CryptoHash>(
+    leaf: CryptoHash,
+    proof: &[CryptoHash],
+    hash: H,
+) -> CryptoHash {
+    proof
+        .iter()
+        .fold(leaf, |node, sibling| hash_node(&node, sibling, &hash))
+}
+
+/// Off-chain helper to build an airdrop tree and the proofs of its leaves.
+#[cfg(not(target_arch = "wasm32"))]
+pub struct MerkleTree {
+    // layers[0] are the leaves, the last layer contains only the root
+    layers: Vec<Vec<CryptoHash>>,
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+impl MerkleTree {
+    pub fn new(leaves: &[VestingCreateParam]) -> Self {
+        assert!(!leaves.is_empty(), "A merkle tree needs at least one leaf.");
+        let mut layers = vec![leaves
+            .iter()
+            .map(|e| hash_leaf(e, sha256))
+            .collect::<Vec<_>>()];
+        while layers.last().unwrap().len() > 1 {
+            // a node without sibling is promoted to the next layer as it is
+            let next = layers
+                .last()
+                .unwrap()
+                .chunks(2)
+                .map(|pair| match pair {
+                    [a, b] => hash_node(a, b, sha256),
+                    [a] => *a,
+                    _ => unreachable!(),
+                })
+                .collect();
+            layers.push(next);
+        }
+        Self { layers }
+    }
+
+    pub fn root(&self) -> CryptoHash {
+        self.layers.last().unwrap()[0]
+    }
+
+    pub fn proof(&self, index: usize) -> Vec<CryptoHash> {
+        assert!(index < self.layers[0].len(), "Leaf index out of range.");
+        let mut proof = vec![];
+        let mut index = index;
+        for layer in &self.layers[..self.layers.len() - 1] {
+            if let Some(sibling) = layer.get(index ^ 1) {
+                proof.push(*sibling);
+            }
+            index /= 2;
+        }
+        proof
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+pub fn sha256(data: &[u8]) -> CryptoHash {
+    use sha2::Digest;
+    sha2::Sha256::digest(data).into()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use near_sdk::env;
+    use near_sdk::test_utils::test_env::bob;
+    use near_sdk::test_utils::VMContextBuilder;
+    use near_sdk::testing_env;
+
+    #[test]
+    fn test_merkle_proof() {
+        testing_env!(VMContextBuilder::new().build());
+
+        let leaves = (1..=5)
+            .map(|i| VestingCreateParam::LinearVesting {
+                beneficiary: bob(),
+                start_time: i,
+                end_time: i + 10,
+                total_vesting_amount: 100,
+            })
+            .collect::<Vec<_>>();
+        let tree = MerkleTree::new(&leaves);
+
+        for (index, leaf) in leaves.iter().enumerate() {
+            // the proof is verified with the hash function used on chain
+            let root = compute_root(
+                hash_leaf(leaf, env::sha256_array),
+                &tree.proof(index),
+                env::sha256_array,
+            );
+            assert_eq!(root, tree.root());
+        }
+
+        let forged_leaf = VestingCreateParam::LinearVesting {
+            beneficiary: bob(),
+            start_time: 1,
+            end_time: 11,
+            total_vesting_amount: 1000,
+        };
+        let root = compute_root(
+            hash_leaf(&forged_leaf, env::sha256_array),
+            &tree.proof(0),
+            env::sha256_array,
+        );
+        assert_ne!(root, tree.root());
+    }
+}
@@ -0,0 +1,163 @@
+use crate::vesting::traits::Beneficiary;
+use crate::*;
+use near_contract_standards::upgrade::Ownable;
+
+/// State layout of the deployments before roles, timelock, multisig, pools and
+/// the beneficiary index were added.
+#[derive(BorshDeserialize, BorshSerialize)]
+pub struct OldTokenVestingContract {
+    pub owner: AccountId,
+    pub token_id: AccountId,
+    pub vestings: UnorderedMap<VestingId, Vesting>,
+    pub uuid: u64,
+}
+
+#[near_bindgen]
+impl TokenVestingContract {
+    /// Migrate the root state of an old deployment, the new features start disabled.
+    /// The vestings are migrated by migrate_vestings afterwards.
+    #[private]
+    #[init(ignore_state)]
+    pub fn migrate() -> Self {
+        let old: OldTokenVestingContract =
+            env::state_read().expect("Failed to read the old contract state.");
+
+        let mut contract = Self::new(old.owner, old.token_id);
+        contract.vestings = old.vestings;
+        contract.uuid = old.uuid;
+        // the existing vestings were created without pools
+        contract.is_pool_required = false;
+        if !contract.vestings.is_empty() {
+            contract.migration_cursor = Some(0);
+        }
+        contract
+    }
+
+    /// Build the beneficiary index and the stats of a page of the existing vestings,
+    /// returns the index of the next page or None if all vestings are migrated.
+    pub fn migrate_vestings(&mut self, from_index: u32, limit: u32) -> Option<u32> {
+        self.assert_owner();
+        let cursor = self
+            .migration_cursor
+            .expect("All vestings are migrated already.");
+        assert_eq!(
+            from_index, cursor,
+            "The migration should continue from index {}.",
+            cursor
+        );
+
+        let vestings = self
+            .vestings
+            .values_as_vector()
+            .iter()
+            .skip(from_index as usize)
+            .take(limit as usize)
+            .collect_vec();
+        for vesting in &vestings {
+            self.internal_index_vesting(&vesting.get_beneficiary(), &vesting.get_vesting_id());
+            self.internal_update_stats(None, Some(vesting));
+        }
+
+        let next_index = from_index as u64 + vestings.len() as u64;
+        self.migration_cursor = if next_index < self.vestings.len() {
+            Some(next_index as u32)
+        } else {
+            None
+        };
+        self.migration_cursor
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::interfaces::{BeneficiaryAction, Viewer};
+    use crate::test::usdc;
+    use crate::vesting::traits::VestingAmount;
+    use crate::vesting::VestingCreateParam;
+    use near_sdk::json_types::U64;
+    use near_sdk::test_utils::test_env::{alice, bob, carol};
+    use near_sdk::test_utils::VMContextBuilder;
+    use near_sdk::testing_env;
+
+    /// Write the state of an old deployment with 2 vestings of bob and 1 of carol.
+    fn setup_old_contract() {
+        testing_env!(VMContextBuilder::new()
+            .predecessor_account_id(alice())
+            .block_timestamp(100 * 1_000_000_000)
+            .build());
+        let mut vestings = UnorderedMap::new(StorageKey::Vestings);
+        for (id, beneficiary) in [(1, bob()), (2, carol()), (3, bob())] {
+            let vesting_id = U64(id);
+            let vesting = Vesting::new(
+                vesting_id,
+                VestingCreateParam::LinearVesting {
+                    beneficiary,
+                    start_time: 50,
+                    end_time: 150,
+                    total_vesting_amount: 100,
+                },
+            );
+            vestings.insert(&vesting_id, &vesting);
+        }
+        env::state_write(&OldTokenVestingContract {
+            owner: alice(),
+            token_id: usdc(),
+            vestings,
+            uuid: 3,
+        });
+    }
+
+    fn vesting_ids_of(contract: &TokenVestingContract, beneficiary: AccountId) -> Vec<u64> {
+        let mut vesting_ids = contract
+            .get_vesting(0, 10, Some(beneficiary), None)
+            .iter()
+            .map(|e| e.get_vesting_id().0)
+            .collect_vec();
+        vesting_ids.sort();
+        vesting_ids
+    }
+
+    #[test]
+    fn test_migrate_vestings() {
+        setup_old_contract();
+        let mut contract = TokenVestingContract::migrate();
+        assert_eq!(contract.migration_cursor, Some(0));
+        assert_eq!(contract.uuid, 3);
+        assert!(!contract.is_pool_required);
+        assert!(TokenVestingContract::new(alice(), usdc()).is_pool_required);
+
+        assert_eq!(contract.migrate_vestings(0, 2), Some(2));
+        assert_eq!(contract.migrate_vestings(2, 2), None);
+        assert_eq!(contract.migration_cursor, None);
+
+        assert_eq!(vesting_ids_of(&contract, bob()), vec![1, 3]);
+        assert_eq!(vesting_ids_of(&contract, carol()), vec![2]);
+        let stats = contract.get_stats();
+        assert_eq!(stats.active_count, 3);
+        assert_eq!(stats.beneficiary_count, 2);
+        assert_eq!(stats.total_committed, 300);
+        assert_eq!(
+            stats.total_released,
+            3 * contract.get_vesting_by_id(U64(1)).get_released_amount()
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "The migration should continue from index 2.")]
+    fn test_migrate_vestings_from_wrong_index() {
+        setup_old_contract();
+        let mut contract = TokenVestingContract::migrate();
+        contract.migrate_vestings(0, 2);
+        contract.migrate_vestings(0, 2);
+    }
+
+    #[test]
+    #[should_panic(expected = "The vestings are not migrated yet.")]
+    fn test_change_before_migrated() {
+        setup_old_contract();
+        let mut contract = TokenVestingContract::migrate();
+        contract.migrate_vestings(0, 2);
+        contract.change_beneficiary(U64(1), carol());
+    }
+}
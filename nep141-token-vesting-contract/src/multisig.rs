@@ -0,0 +1,186 @@
+use crate::events::{EventEmit, UserAction};
+use crate::interfaces::Multisig;
+use crate::timelock::SensitiveAction;
+use crate::types::{ProposalId, SecondTimeStamp};
+use crate::*;
+use near_contract_standards::upgrade::Ownable;
+
+#[derive(BorshSerialize, BorshDeserialize, Debug, Serialize, Deserialize, Clone)]
+#[serde(crate = "near_sdk::serde")]
+pub struct Proposal {
+    pub id: ProposalId,
+    pub action: SensitiveAction,
+    pub proposer: AccountId,
+    pub approvals: Vec<AccountId>,
+    #[serde(with = "u64_dec_format")]
+    pub create_time: SecondTimeStamp,
+}
+
+#[near_bindgen]
+impl Multisig for TokenVestingContract {
+    #[payable]
+    fn propose_action(&mut self, action: SensitiveAction) -> ProposalId {
+        self.assert_approver();
+        assert!(
+            action.requires_approval(),
+            "This action doesn't need to be approved by approvers."
+        );
+        self.assert_valid_action(&action);
+        let prev_storage = env::storage_usage();
+
+        let id = self.internal_assign_id();
+        let proposer = env::predecessor_account_id();
+        let proposal = Proposal {
+            id,
+            action,
+            proposer: proposer.clone(),
+            approvals: vec![],
+            create_time: get_block_second_time(),
+        };
+        self.proposals.insert(&id, &proposal);
+        UserAction::ProposeAction {
+            proposal_id: &id,
+            action: &proposal.action,
+        }
+        .emit();
+
+        self.internal_approve_proposal(&id, &proposer);
+        self.internal_check_storage(prev_storage);
+        id
+    }
+
+    #[payable]
+    fn approve_proposal(&mut self, proposal_id: ProposalId) {
+        self.assert_approver();
+        let prev_storage = env::storage_usage();
+
+        self.internal_approve_proposal(&proposal_id, &env::predecessor_account_id());
+        self.internal_check_storage(prev_storage);
+    }
+
+    fn cancel_proposal(&mut self, proposal_id: ProposalId) {
+        let proposal = self
+            .proposals
+            .get(&proposal_id)
+            .unwrap_or_else(|| panic!("No such proposal id: #{}.", proposal_id.0));
+        assert_eq!(
+            proposal.proposer,
+            env::predecessor_account_id(),
+            "Only the proposer can cancel a proposal."
+        );
+
+        self.proposals.remove(&proposal_id);
+        UserAction::CancelProposal {
+            proposal_id: &proposal_id,
+        }
+        .emit();
+    }
+
+    #[payable]
+    fn set_approvers(&mut self, approvers: Vec<AccountId>, threshold: u32) {
+        self.assert_owner();
+        self.assert_no_timelock();
+        self.assert_no_multisig();
+        let prev_storage = env::storage_usage();
+
+        self.internal_set_approvers(approvers, threshold);
+        self.internal_check_storage(prev_storage);
+    }
+}
+
+impl SensitiveAction {
+    /// Whether the action must be approved by approvers when multisig is enabled.
+    pub fn requires_approval(&self) -> bool {
+        match self {
+            SensitiveAction::TerminateVesting { .. }
+            | SensitiveAction::Withdraw { .. }
+            | SensitiveAction::SetOwner { .. }
+            | SensitiveAction::SetApprovers { .. } => true,
+            SensitiveAction::AmendVesting { .. } | SensitiveAction::SetTimelockDelay { .. } => {
+                false
+            }
+        }
+    }
+}
+
+impl TokenVestingContract {
+    pub(crate) fn is_multisig_enabled(&self) -> bool {
+        self.approval_threshold > 0
+    }
+
+    pub(crate) fn assert_approver(&self) {
+        assert!(
+            self.approvers.contains(&env::predecessor_account_id()),
+            "Only approvers can perform this action."
+        );
+    }
+
+    /// Sensitive actions can only be called directly when multisig is disabled.
+    pub(crate) fn assert_no_multisig(&self) {
+        assert!(
+            !self.is_multisig_enabled(),
+            "This action should be proposed to approvers."
+        );
+    }
+
+    pub(crate) fn internal_set_approvers(&mut self, approvers: Vec<AccountId>, threshold: u32) {
+        assert!(
+            threshold as usize <= approvers.len(),
+            "The threshold should be less or eq than the number of approvers."
+        );
+        assert!(
+            threshold > 0 || approvers.is_empty(),
+            "The threshold should be greater than 0 when approvers are set."
+        );
+        self.approvers.clear();
+        for approver in &approvers {
+            self.approvers.insert(approver);
+        }
+        self.approval_threshold = threshold;
+
+        UserAction::SetApprovers {
+            approvers: &approvers,
+            threshold: &threshold,
+        }
+        .emit();
+    }
+
+    /// Record the approval, and dispatch the action once the threshold is reached.
+    /// The action is scheduled if the timelock is enabled, otherwise it is executed at once.
+    fn internal_approve_proposal(&mut self, proposal_id: &ProposalId, approver: &AccountId) {
+        let mut proposal = self
+            .proposals
+            .get(proposal_id)
+            .unwrap_or_else(|| panic!("No such proposal id: #{}.", proposal_id.0));
+        assert!(
+            !proposal.approvals.contains(approver),
+            "The proposal has been approved by {}.",
+            approver
+        );
+        proposal.approvals.push(approver.clone());
+        UserAction::ApproveProposal {
+            proposal_id,
+            approver,
+        }
+        .emit();
+
+        // approvals of removed approvers don't count
+        let approval_count = proposal
+            .approvals
+            .iter()
+            .filter(|e| self.approvers.contains(e))
+            .count();
+        if approval_count < self.approval_threshold as usize {
+            self.proposals.insert(proposal_id, &proposal);
+            return;
+        }
+
+        self.proposals.remove(proposal_id);
+        UserAction::ExecuteProposal { proposal_id }.emit();
+        if self.timelock_delay > 0 {
+            self.internal_schedule_action(proposal.action, proposal.proposer);
+        } else {
+            self.internal_execute_sensitive_action(proposal.action);
+        }
+    }
+}
@@ -1,7 +1,11 @@
 use crate::events::{EventEmit, UserAction, VestingEvent};
+use crate::roles::Role;
+use crate::template::VestingTemplate;
+use crate::types::{PoolId, TemplateId};
 use crate::vesting::cliff::CliffVestingCheckpoint;
+use crate::vesting::metadata::VestingMetadata;
 use crate::vesting::traits::Frozen;
-use crate::vesting::VestingCreateParam;
+use crate::vesting::{BulkActionResult, VestingCreateParam, VestingSelector};
 use crate::*;
 use crate::{OwnerAction, TokenVestingContract, VestingId};
 use near_contract_standards::upgrade::Ownable;
@@ -15,6 +19,8 @@ impl Ownable for TokenVestingContract {
 
     fn set_owner(&mut self, owner: AccountId) {
         self.assert_owner();
+        self.assert_no_timelock();
+        self.assert_no_multisig();
         self.owner = owner;
     }
 }
@@ -28,13 +34,19 @@ impl OwnerAction for TokenVestingContract {
         start_time: U64,
         end_time: U64,
         total_vesting_amount: U128,
+        pool_id: Option<PoolId>,
+        metadata: Option<VestingMetadata>,
     ) -> VestingId {
-        self.internal_create_vesting(VestingCreateParam::LinearVesting {
-            beneficiary,
-            start_time: start_time.0,
-            end_time: end_time.0,
-            total_vesting_amount: total_vesting_amount.0,
-        })
+        self.internal_create_vesting(
+            VestingCreateParam::LinearVesting {
+                beneficiary,
+                start_time: start_time.0,
+                end_time: end_time.0,
+                total_vesting_amount: total_vesting_amount.0,
+            },
+            pool_id,
+            metadata,
+        )
     }
 
     #[payable]
@@ -42,15 +54,108 @@ impl OwnerAction for TokenVestingContract {
         &mut self,
         beneficiary: AccountId,
         time_cliff_list: Vec<CliffVestingCheckpoint>,
+        pool_id: Option<PoolId>,
+        metadata: Option<VestingMetadata>,
     ) -> VestingId {
-        self.internal_create_vesting(VestingCreateParam::CliffVesting {
-            beneficiary,
-            time_cliff_list,
-        })
+        self.internal_create_vesting(
+            VestingCreateParam::CliffVesting {
+                beneficiary,
+                time_cliff_list,
+            },
+            pool_id,
+            metadata,
+        )
+    }
+
+    #[payable]
+    fn create_vestings(
+        &mut self,
+        params: Vec<VestingCreateParam>,
+        pool_id: Option<PoolId>,
+    ) -> Vec<VestingId> {
+        self.internal_create_vestings(params, pool_id)
+    }
+
+    #[payable]
+    fn set_vesting_template(&mut self, template_id: TemplateId, template: VestingTemplate) {
+        self.assert_role(Role::Admin);
+        template.assert_valid();
+        let prev_storage = env::storage_usage();
+
+        self.templates.insert(&template_id, &template);
+        self.internal_check_storage(prev_storage);
+
+        UserAction::SetVestingTemplate {
+            template_id: &template_id,
+            template: &template,
+        }
+        .emit();
+    }
+
+    fn remove_vesting_template(&mut self, template_id: TemplateId) {
+        self.assert_role(Role::Admin);
+        assert!(
+            self.templates.remove(&template_id).is_some(),
+            "No such vesting template: {}.",
+            template_id
+        );
+
+        UserAction::RemoveVestingTemplate {
+            template_id: &template_id,
+        }
+        .emit();
+    }
+
+    #[payable]
+    fn create_vesting_from_template(
+        &mut self,
+        template_id: TemplateId,
+        beneficiary: AccountId,
+        start_time: U64,
+        amount: U128,
+        pool_id: Option<PoolId>,
+        metadata: Option<VestingMetadata>,
+    ) -> VestingId {
+        let template = self
+            .templates
+            .get(&template_id)
+            .unwrap_or_else(|| panic!("No such vesting template: {}.", template_id));
+
+        let vesting_id = self.internal_create_vesting(
+            template.to_create_param(beneficiary, start_time.0, amount.0),
+            pool_id,
+            metadata,
+        );
+        UserAction::CreateVestingFromTemplate {
+            template_id: &template_id,
+            vesting_id: &vesting_id,
+        }
+        .emit();
+        vesting_id
+    }
+
+    #[payable]
+    fn set_vesting_metadata(&mut self, vesting_id: VestingId, metadata: Option<VestingMetadata>) {
+        self.assert_role(Role::Admin);
+        assert!(
+            self.vestings.get(&vesting_id).is_some(),
+            "No such vesting id: #{}.",
+            vesting_id.0
+        );
+        let prev_storage = env::storage_usage();
+
+        self.internal_set_vesting_metadata(&vesting_id, metadata.as_ref());
+        self.internal_check_storage(prev_storage);
+
+        UserAction::SetVestingMetadata {
+            vesting_id: &vesting_id,
+            metadata: &metadata,
+        }
+        .emit();
     }
 
     fn freeze_vesting(&mut self, vesting_id: VestingId) {
-        self.assert_owner();
+        self.assert_role(Role::Freezer);
         self.internal_use_vesting(&vesting_id, |vesting| vesting.freeze());
         VestingEvent::UpdateVesting {
             vesting: &self
@@ -65,7 +170,7 @@ impl OwnerAction for TokenVestingContract {
     }
 
     fn unfreeze_vesting(&mut self, vesting_id: VestingId) {
-        self.assert_owner();
+        self.assert_role(Role::Freezer);
 
         self.internal_use_vesting(&vesting_id, |vesting| vesting.unfreeze());
         VestingEvent::UpdateVesting {
@@ -81,13 +186,167 @@ impl OwnerAction for TokenVestingContract {
     }
 
     fn terminate_vesting(&mut self, vesting_id: VestingId) {
-        self.assert_owner();
-
-        self.vestings.remove(&vesting_id);
+        self.assert_role(Role::Terminator);
+        self.assert_no_timelock();
+        self.assert_no_multisig();
 
+        self.internal_terminate_vesting(&vesting_id);
         UserAction::TerminateVesting {
             vesting_id: &vesting_id,
         }
         .emit();
     }
+
+    fn bulk_freeze_vesting(
+        &mut self,
+        selector: VestingSelector,
+        from_index: u32,
+        limit: u32,
+    ) -> BulkActionResult {
+        self.assert_role(Role::Freezer);
+
+        let (vesting_ids, is_last_page) =
+            self.internal_select_vesting_ids(&selector, from_index, limit);
+        for vesting_id in &vesting_ids {
+            let vesting = self.internal_use_vesting(vesting_id, |vesting| {
+                vesting.freeze();
+                vesting.clone()
+            });
+            VestingEvent::UpdateVesting { vesting: &vesting }.emit();
+        }
+        UserAction::BulkFreezeVesting {
+            vesting_ids: &vesting_ids,
+        }
+        .emit();
+        BulkActionResult {
+            vesting_ids,
+            next_index: if is_last_page {
+                None
+            } else {
+                Some(from_index + limit)
+            },
+        }
+    }
+
+    fn bulk_unfreeze_vesting(
+        &mut self,
+        selector: VestingSelector,
+        from_index: u32,
+        limit: u32,
+    ) -> BulkActionResult {
+        self.assert_role(Role::Freezer);
+
+        let (vesting_ids, is_last_page) =
+            self.internal_select_vesting_ids(&selector, from_index, limit);
+        for vesting_id in &vesting_ids {
+            let vesting = self.internal_use_vesting(vesting_id, |vesting| {
+                vesting.unfreeze();
+                vesting.clone()
+            });
+            VestingEvent::UpdateVesting { vesting: &vesting }.emit();
+        }
+        UserAction::BulkUnfreezeVesting {
+            vesting_ids: &vesting_ids,
+        }
+        .emit();
+        BulkActionResult {
+            vesting_ids,
+            next_index: if is_last_page {
+                None
+            } else {
+                Some(from_index + limit)
+            },
+        }
+    }
+
+    fn bulk_terminate_vesting(
+        &mut self,
+        selector: VestingSelector,
+        from_index: u32,
+        limit: u32,
+    ) -> BulkActionResult {
+        self.assert_role(Role::Terminator);
+        self.assert_no_timelock();
+        self.assert_no_multisig();
+
+        let (vesting_ids, is_last_page) =
+            self.internal_select_vesting_ids(&selector, from_index, limit);
+        for vesting_id in &vesting_ids {
+            self.internal_terminate_vesting(vesting_id);
+        }
+        UserAction::BulkTerminateVesting {
+            vesting_ids: &vesting_ids,
+        }
+        .emit();
+        // the terminated vestings leave the index of the beneficiary, which is paged from the end
+        let removed_count = match selector {
+            VestingSelector::Beneficiary(_) => vesting_ids.len() as u32,
+            VestingSelector::VestingIds(_) => 0,
+        };
+        BulkActionResult {
+            vesting_ids,
+            next_index: if is_last_page {
+                None
+            } else {
+                Some(from_index + limit - removed_count)
+            },
+        }
+    }
+
+    #[payable]
+    fn grant_role(&mut self, role: Role, account_id: AccountId) {
+        self.assert_owner();
+        let prev_storage = env::storage_usage();
+
+        if self.internal_grant_role(role, &account_id) {
+            UserAction::GrantRole {
+                role: &role,
+                account_id: &account_id,
+            }
+            .emit();
+        }
+        self.internal_check_storage(prev_storage);
+    }
+
+    fn revoke_role(&mut self, role: Role, account_id: AccountId) {
+        self.assert_owner();
+
+        if self.internal_revoke_role(role, &account_id) {
+            UserAction::RevokeRole {
+                role: &role,
+                account_id: &account_id,
+            }
+            .emit();
+        }
+    }
+
+    fn pause_contract(&mut self) {
+        self.assert_role(Role::Guardian);
+        assert!(!self.is_paused, "The contract is already paused.");
+
+        self.is_paused = true;
+        UserAction::PauseContract {
+            account_id: &env::predecessor_account_id(),
+        }
+        .emit();
+    }
+
+    fn resume_contract(&mut self) {
+        // a guardian can only pause, so that a compromised guardian can't undo a pause
+        self.assert_role(Role::Admin);
+        assert!(self.is_paused, "The contract is not paused.");
+
+        self.is_paused = false;
+        UserAction::ResumeContract {
+            account_id: &env::predecessor_account_id(),
+        }
+        .emit();
+    }
+
+    fn set_archive_enabled(&mut self, enabled: bool) {
+        self.assert_role(Role::Admin);
+        self.is_archive_enabled = enabled;
+
+        UserAction::SetArchiveEnabled { enabled: &enabled }.emit();
+    }
 }
@@ -0,0 +1,230 @@
+use crate::constants::POOL_UTILIZATION_DENOMINATOR;
+use crate::events::{EventEmit, UserAction};
+use crate::interfaces::VestingPoolAction;
+use crate::types::{PoolId, SecondTimeStamp, U256};
+use crate::vesting::metadata::VestingMetadata;
+use crate::vesting::traits::VestingTokenInfoTrait;
+use crate::vesting::VestingCreateParam;
+use crate::*;
+use near_contract_standards::fungible_token::receiver::FungibleTokenReceiver;
+use near_contract_standards::upgrade::Ownable;
+use near_sdk::PromiseOrValue;
+
+/// A budget bucket, vestings created against a pool can't allocate more than its cap.
+#[derive(BorshSerialize, BorshDeserialize, Debug, Serialize, Deserialize, Clone)]
+#[serde(crate = "near_sdk::serde")]
+pub struct VestingPool {
+    pub id: PoolId,
+    #[serde(with = "u128_dec_format")]
+    pub cap: Balance,
+    // tokens transferred to the contract for this pool
+    #[serde(with = "u128_dec_format")]
+    pub funded_amount: Balance,
+    // total amount of the vestings (and airdrops) created against this pool,
+    // minus the amount returned by terminations
+    #[serde(with = "u128_dec_format")]
+    pub allocated_amount: Balance,
+    #[serde(with = "u64_dec_format")]
+    pub create_time: SecondTimeStamp,
+}
+
+/// A pool with the amount which can still be allocated and its utilization.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(crate = "near_sdk::serde")]
+pub struct VestingPoolView {
+    pub id: PoolId,
+    #[serde(with = "u128_dec_format")]
+    pub cap: Balance,
+    #[serde(with = "u128_dec_format")]
+    pub funded_amount: Balance,
+    #[serde(with = "u128_dec_format")]
+    pub allocated_amount: Balance,
+    // cap - allocated amount
+    #[serde(with = "u128_dec_format")]
+    pub remaining_amount: Balance,
+    // allocated amount / cap in basis points
+    pub utilization: u32,
+    #[serde(with = "u64_dec_format")]
+    pub create_time: SecondTimeStamp,
+}
+
+impl VestingPool {
+    pub fn get_view(&self) -> VestingPoolView {
+        VestingPoolView {
+            id: self.id.clone(),
+            cap: self.cap,
+            funded_amount: self.funded_amount,
+            allocated_amount: self.allocated_amount,
+            remaining_amount: self.cap - self.allocated_amount,
+            utilization: if self.cap == 0 {
+                0
+            } else {
+                (U256::from(self.allocated_amount) * U256::from(POOL_UTILIZATION_DENOMINATOR)
+                    / U256::from(self.cap))
+                .as_u32()
+            },
+            create_time: self.create_time,
+        }
+    }
+}
+
+#[near_bindgen]
+impl VestingPoolAction for TokenVestingContract {
+    #[payable]
+    fn create_vesting_pool(&mut self, pool_id: PoolId, cap: U128) {
+        self.assert_role(Role::Admin);
+        assert!(
+            self.pools.get(&pool_id).is_none(),
+            "The vesting pool {} already exists.",
+            pool_id
+        );
+        let prev_storage = env::storage_usage();
+
+        self.pools.insert(
+            &pool_id,
+            &VestingPool {
+                id: pool_id.clone(),
+                cap: cap.0,
+                funded_amount: 0,
+                allocated_amount: 0,
+                create_time: get_block_second_time(),
+            },
+        );
+        self.internal_check_storage(prev_storage);
+
+        UserAction::CreateVestingPool {
+            pool_id: &pool_id,
+            cap: &cap,
+        }
+        .emit();
+    }
+
+    fn set_vesting_pool_cap(&mut self, pool_id: PoolId, cap: U128) {
+        self.assert_role(Role::Admin);
+        let mut pool = self.internal_get_pool(&pool_id);
+        assert!(
+            cap.0 >= pool.allocated_amount,
+            "The cap should be greater or eq than the allocated amount {}.",
+            pool.allocated_amount
+        );
+
+        pool.cap = cap.0;
+        self.pools.insert(&pool_id, &pool);
+        UserAction::SetVestingPoolCap {
+            pool_id: &pool_id,
+            cap: &cap,
+        }
+        .emit();
+    }
+
+    fn remove_vesting_pool(&mut self, pool_id: PoolId) {
+        self.assert_role(Role::Admin);
+        let pool = self.internal_get_pool(&pool_id);
+        assert_eq!(
+            pool.allocated_amount, 0,
+            "Failed to remove the vesting pool because it still has allocations."
+        );
+
+        self.pools.remove(&pool_id);
+        UserAction::RemoveVestingPool { pool_id: &pool_id }.emit();
+    }
+
+    fn set_pool_required(&mut self, required: bool) {
+        self.assert_owner();
+        self.is_pool_required = required;
+
+        UserAction::SetPoolRequired {
+            required: &required,
+        }
+        .emit();
+    }
+}
+
+#[near_bindgen]
+impl FungibleTokenReceiver for TokenVestingContract {
+    /// Vesting tokens transferred with a pool id as msg fund that pool,
+    /// tokens transferred with an empty msg are kept without being assigned to any pool.
+    fn ft_on_transfer(
+        &mut self,
+        sender_id: AccountId,
+        amount: U128,
+        msg: String,
+    ) -> PromiseOrValue<U128> {
+        assert_eq!(
+            env::predecessor_account_id(),
+            self.token_id,
+            "Only the vesting token can be transferred to this contract."
+        );
+        if msg.is_empty() {
+            return PromiseOrValue::Value(U128(0));
+        }
+
+        let mut pool = self.internal_get_pool(&msg);
+        pool.funded_amount += amount.0;
+        self.pools.insert(&msg, &pool);
+
+        UserAction::FundVestingPool {
+            pool_id: &msg,
+            sender_id: &sender_id,
+            amount: &amount,
+        }
+        .emit();
+        PromiseOrValue::Value(U128(0))
+    }
+}
+
+impl TokenVestingContract {
+    pub(crate) fn internal_get_pool(&self, pool_id: &PoolId) -> VestingPool {
+        self.pools
+            .get(pool_id)
+            .unwrap_or_else(|| panic!("No such vesting pool: {}.", pool_id))
+    }
+
+    /// Allocate amount from the pool, panics if the cap would be exceeded.
+    pub(crate) fn internal_allocate_from_pool(&mut self, pool_id: &PoolId, amount: Balance) {
+        let mut pool = self.internal_get_pool(pool_id);
+        pool.allocated_amount += amount;
+        assert!(
+            pool.allocated_amount <= pool.cap,
+            "The cap of vesting pool {} is exceeded.",
+            pool_id
+        );
+        self.pools.insert(pool_id, &pool);
+    }
+
+    /// Return amount to the pool, a removed pool has nothing to return to.
+    pub(crate) fn internal_return_to_pool(&mut self, pool_id: &PoolId, amount: Balance) {
+        if let Some(mut pool) = self.pools.get(pool_id) {
+            pool.allocated_amount -= amount;
+            self.pools.insert(pool_id, &pool);
+        }
+    }
+
+    pub(crate) fn assert_pool_given(&self, pool_id: &Option<PoolId>) {
+        assert!(
+            !self.is_pool_required || pool_id.is_some(),
+            "A vesting pool is required."
+        );
+    }
+
+    /// Create the vesting against the pool, the total amount of the vesting is allocated from it.
+    pub(crate) fn internal_insert_pool_vesting(
+        &mut self,
+        param: VestingCreateParam,
+        pool_id: &Option<PoolId>,
+        metadata: Option<VestingMetadata>,
+    ) -> VestingId {
+        self.assert_pool_given(pool_id);
+        let vesting_id = self.internal_insert_vesting(param, metadata);
+        if let Some(pool_id) = pool_id {
+            let amount = self
+                .internal_get_vesting(&vesting_id)
+                .unwrap()
+                .get_vesting_token_info()
+                .total_vesting_amount;
+            self.internal_allocate_from_pool(pool_id, amount);
+            self.vesting_pools.insert(&vesting_id, pool_id);
+        }
+        vesting_id
+    }
+}
@@ -0,0 +1,68 @@
+use crate::*;
+use near_contract_standards::upgrade::Ownable;
+
+#[derive(
+    BorshSerialize, BorshDeserialize, Debug, Serialize, Deserialize, Clone, Copy, Eq, PartialEq,
+)]
+#[serde(crate = "near_sdk::serde")]
+pub enum Role {
+    /// Can create vestings.
+    Creator,
+    /// Can freeze and unfreeze vestings.
+    Freezer,
+    /// Can terminate vestings.
+    Terminator,
+    /// Has the permissions of all other roles.
+    Admin,
+    /// Can pause the whole contract.
+    Guardian,
+}
+
+impl TokenVestingContract {
+    /// Check that the predecessor is the owner, an admin or a member of the given role.
+    pub(crate) fn assert_role(&self, role: Role) {
+        let account_id = env::predecessor_account_id();
+        assert!(
+            account_id.eq(&self.get_owner())
+                || self.internal_has_role(Role::Admin, &account_id)
+                || self.internal_has_role(role, &account_id),
+            "Owner must be predecessor or predecessor must have role {:?}.",
+            role
+        );
+    }
+
+    pub(crate) fn internal_has_role(&self, role: Role, account_id: &AccountId) -> bool {
+        self.role_members
+            .get(&role)
+            .map(|members| members.contains(account_id))
+            .unwrap_or(false)
+    }
+
+    pub(crate) fn internal_grant_role(&mut self, role: Role, account_id: &AccountId) -> bool {
+        let mut members = self
+            .role_members
+            .get(&role)
+            .unwrap_or_else(|| UnorderedSet::new(StorageKey::RoleMembersInner { role }));
+        let inserted = members.insert(account_id);
+        self.role_members.insert(&role, &members);
+        inserted
+    }
+
+    pub(crate) fn internal_revoke_role(&mut self, role: Role, account_id: &AccountId) -> bool {
+        match self.role_members.get(&role) {
+            Some(mut members) => {
+                let removed = members.remove(account_id);
+                self.role_members.insert(&role, &members);
+                removed
+            }
+            None => false,
+        }
+    }
+
+    pub(crate) fn internal_get_role_members(&self, role: Role) -> Vec<AccountId> {
+        self.role_members
+            .get(&role)
+            .map(|members| members.to_vec())
+            .unwrap_or_default()
+    }
+}
@@ -0,0 +1,79 @@
+use crate::vesting::traits::{Frozen, VestingAmount, VestingTokenInfoTrait};
+use crate::*;
+
+/// Contract-wide statistics, maintained when vestings are saved and removed.
+#[derive(
+    BorshSerialize, BorshDeserialize, Debug, Serialize, Deserialize, Clone, Default, PartialEq,
+)]
+#[serde(crate = "near_sdk::serde")]
+pub struct VestingStats {
+    #[serde(with = "u64_dec_format")]
+    pub active_count: u64,
+    #[serde(with = "u64_dec_format")]
+    pub frozen_count: u64,
+    #[serde(with = "u64_dec_format")]
+    pub finished_count: u64,
+    #[serde(with = "u64_dec_format")]
+    pub terminated_count: u64,
+    // beneficiaries of the existing vestings
+    #[serde(with = "u64_dec_format")]
+    pub beneficiary_count: u64,
+    // total amount of all vestings ever created, after amendments
+    #[serde(with = "u128_dec_format")]
+    pub total_committed: Balance,
+    // the released amount of each vesting as of the last time it was saved
+    #[serde(with = "u128_dec_format")]
+    pub total_released: Balance,
+    #[serde(with = "u128_dec_format")]
+    pub total_claimed: Balance,
+    // the amount not claimed yet of the terminated vestings
+    #[serde(with = "u128_dec_format")]
+    pub total_clawed_back: Balance,
+}
+
+impl TokenVestingContract {
+    /// Update the statistics by the change of a vesting, old is None if the vesting
+    /// is created, and new is None if the vesting is removed. The amounts of a removed
+    /// vesting stay in the totals.
+    pub(crate) fn internal_update_stats(&mut self, old: Option<&Vesting>, new: Option<&Vesting>) {
+        let stats = &mut self.stats;
+        if let Some(old) = old {
+            if old.is_frozen() {
+                stats.frozen_count -= 1;
+            } else {
+                stats.active_count -= 1;
+            }
+        }
+        match new {
+            Some(new) => {
+                if new.is_frozen() {
+                    stats.frozen_count += 1;
+                } else {
+                    stats.active_count += 1;
+                }
+                let token_info = new.get_vesting_token_info();
+                stats.total_committed += token_info.total_vesting_amount;
+                stats.total_claimed += token_info.claimed_token_amount;
+                if let Some(old) = old {
+                    let token_info = old.get_vesting_token_info();
+                    stats.total_committed -= token_info.total_vesting_amount;
+                    stats.total_claimed -= token_info.claimed_token_amount;
+                }
+
+                let vesting_id = new.get_vesting_id();
+                let released_amount = new.get_released_amount();
+                let prev_released_amount = self
+                    .released_snapshots
+                    .insert(&vesting_id, &released_amount)
+                    .unwrap_or(0);
+                self.stats.total_released =
+                    self.stats.total_released + released_amount - prev_released_amount;
+            }
+            None => {
+                if let Some(old) = old {
+                    self.released_snapshots.remove(&old.get_vesting_id());
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,255 @@
+use crate::constants::STORAGE_ACCOUNT_BYTES;
+use crate::events::{EventEmit, UserAction};
+use crate::*;
+use near_contract_standards::storage_management::{
+    StorageBalance, StorageBalanceBounds, StorageManagement,
+};
+use near_sdk::assert_one_yocto;
+
+/// The storage deposit of an account, the locked part pays for the registration
+/// and the vestings created by the account.
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone)]
+pub struct StorageAccount {
+    pub available: Balance,
+    pub locked: Balance,
+}
+
+/// The account which paid for the storage of a vesting, and how much it paid.
+#[derive(BorshSerialize, BorshDeserialize, Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(crate = "near_sdk::serde")]
+pub struct VestingStorage {
+    pub payer: AccountId,
+    #[serde(with = "u128_dec_format")]
+    pub amount: Balance,
+}
+
+#[near_bindgen]
+impl StorageManagement for TokenVestingContract {
+    #[payable]
+    fn storage_deposit(
+        &mut self,
+        account_id: Option<AccountId>,
+        registration_only: Option<bool>,
+    ) -> StorageBalance {
+        let amount = env::attached_deposit();
+        let account_id = account_id.unwrap_or_else(env::predecessor_account_id);
+        let registration_only = registration_only.unwrap_or(false);
+
+        let deposit = match self.storage_accounts.get(&account_id) {
+            Some(mut account) => {
+                if registration_only {
+                    log!("The account is already registered, refunding the deposit.");
+                    0
+                } else {
+                    account.available += amount;
+                    self.storage_accounts.insert(&account_id, &account);
+                    amount
+                }
+            }
+            None => {
+                let min_balance = self.storage_balance_bounds().min.0;
+                assert!(
+                    amount >= min_balance,
+                    "The attached deposit is less than the minimum storage balance {}.",
+                    min_balance
+                );
+                let deposit = if registration_only {
+                    min_balance
+                } else {
+                    amount
+                };
+                self.storage_accounts.insert(
+                    &account_id,
+                    &StorageAccount {
+                        available: deposit - min_balance,
+                        locked: min_balance,
+                    },
+                );
+                deposit
+            }
+        };
+        if amount > deposit {
+            Promise::new(env::predecessor_account_id()).transfer(amount - deposit);
+        }
+
+        UserAction::StorageDeposit {
+            account_id: &account_id,
+            amount: &U128(deposit),
+        }
+        .emit();
+        self.internal_get_storage_balance(&account_id).unwrap()
+    }
+
+    #[payable]
+    fn storage_withdraw(&mut self, amount: Option<U128>) -> StorageBalance {
+        assert_one_yocto();
+        let account_id = env::predecessor_account_id();
+        let mut account = self
+            .storage_accounts
+            .get(&account_id)
+            .unwrap_or_else(|| panic!("The account {} is not registered.", account_id));
+        let amount = amount.map_or(account.available, |e| e.0);
+        assert!(
+            amount <= account.available,
+            "The amount is greater than the available storage balance {}.",
+            account.available
+        );
+        account.available -= amount;
+        self.storage_accounts.insert(&account_id, &account);
+        if amount > 0 {
+            Promise::new(account_id.clone()).transfer(amount);
+        }
+
+        UserAction::StorageWithdraw {
+            account_id: &account_id,
+            amount: &U128(amount),
+        }
+        .emit();
+        self.internal_get_storage_balance(&account_id).unwrap()
+    }
+
+    #[payable]
+    fn storage_unregister(&mut self, force: Option<bool>) -> bool {
+        assert_one_yocto();
+        assert!(
+            !force.unwrap_or(false),
+            "Force unregistering is not supported."
+        );
+        let account_id = env::predecessor_account_id();
+        match self.storage_accounts.get(&account_id) {
+            Some(account) => {
+                assert!(
+                    account.locked <= self.storage_balance_bounds().min.0,
+                    "Can't unregister while the account pays for the storage of vestings."
+                );
+                self.storage_accounts.remove(&account_id);
+                let amount = account.available + account.locked;
+                Promise::new(account_id.clone()).transfer(amount);
+
+                UserAction::StorageWithdraw {
+                    account_id: &account_id,
+                    amount: &U128(amount),
+                }
+                .emit();
+                true
+            }
+            None => false,
+        }
+    }
+
+    fn storage_balance_bounds(&self) -> StorageBalanceBounds {
+        StorageBalanceBounds {
+            min: U128(STORAGE_ACCOUNT_BYTES as Balance * env::storage_byte_cost()),
+            max: None,
+        }
+    }
+
+    fn storage_balance_of(&self, account_id: AccountId) -> Option<StorageBalance> {
+        self.internal_get_storage_balance(&account_id)
+    }
+}
+
+impl TokenVestingContract {
+    fn internal_get_storage_balance(&self, account_id: &AccountId) -> Option<StorageBalance> {
+        self.storage_accounts
+            .get(account_id)
+            .map(|account| StorageBalance {
+                total: U128(account.available + account.locked),
+                available: U128(account.available),
+            })
+    }
+
+    /// Pay the amount by the available storage balance of the account,
+    /// returns false if it is not registered or the balance is not enough.
+    pub(crate) fn internal_use_storage_balance(
+        &mut self,
+        account_id: &AccountId,
+        amount: Balance,
+    ) -> bool {
+        match self.storage_accounts.get(account_id) {
+            Some(mut account) if account.available >= amount => {
+                account.available -= amount;
+                self.storage_accounts.insert(account_id, &account);
+                true
+            }
+            _ => false,
+        }
+    }
+
+    /// Charge the storage taken by the created vestings, and record the predecessor
+    /// as the payer of each vesting, so that it can be refunded when the vesting is removed.
+    pub(crate) fn internal_charge_vesting_storage(
+        &mut self,
+        prev_storage: StorageUsage,
+        vesting_ids: &[VestingId],
+    ) {
+        let payer = env::predecessor_account_id();
+        // the records are inserted first, so that their own storage is charged too
+        for vesting_id in vesting_ids {
+            self.vesting_storage.insert(
+                vesting_id,
+                &VestingStorage {
+                    payer: payer.clone(),
+                    amount: 0,
+                },
+            );
+        }
+        let storage_cost = self.internal_check_storage(prev_storage);
+
+        let count = vesting_ids.len() as Balance;
+        for (i, vesting_id) in vesting_ids.iter().enumerate() {
+            // the last vesting takes the remainder
+            let amount = if i as Balance == count - 1 {
+                storage_cost - storage_cost / count * (count - 1)
+            } else {
+                storage_cost / count
+            };
+            self.vesting_storage.insert(
+                vesting_id,
+                &VestingStorage {
+                    payer: payer.clone(),
+                    amount,
+                },
+            );
+        }
+        if let Some(mut account) = self.storage_accounts.get(&payer) {
+            account.locked += storage_cost;
+            self.storage_accounts.insert(&payer, &account);
+        }
+    }
+
+    /// Refund the storage paid for the vesting to the storage balance of the payer,
+    /// or to the payer account directly if it is not registered. The cost of the
+    /// archived storage is kept by the contract.
+    pub(crate) fn internal_refund_vesting_storage(
+        &mut self,
+        vesting_id: &VestingId,
+        archived_storage: StorageUsage,
+    ) {
+        if let Some(VestingStorage { payer, amount }) = self.vesting_storage.remove(vesting_id) {
+            let refund =
+                amount.saturating_sub(archived_storage as Balance * env::storage_byte_cost());
+            if let Some(mut account) = self.storage_accounts.get(&payer) {
+                // the registration stays locked if the account registered after paying
+                account.locked = account
+                    .locked
+                    .saturating_sub(amount)
+                    .max(self.storage_balance_bounds().min.0);
+                account.available += refund;
+                self.storage_accounts.insert(&payer, &account);
+            } else if refund > 0 {
+                Promise::new(payer.clone()).transfer(refund);
+            }
+            if refund == 0 {
+                return;
+            }
+
+            UserAction::RefundVestingStorage {
+                vesting_id,
+                account_id: &payer,
+                amount: &U128(refund),
+            }
+            .emit();
+        }
+    }
+}
@@ -0,0 +1,150 @@
+use crate::constants::TEMPLATE_RATIO_DENOMINATOR;
+use crate::types::{SecondTimeStamp, TemplateId, U256};
+use crate::vesting::cliff::CliffVestingCheckpoint;
+use crate::vesting::VestingCreateParam;
+use crate::*;
+
+/// A schedule shape relative to a start time and a total amount.
+#[derive(BorshSerialize, BorshDeserialize, Debug, Serialize, Deserialize, Clone)]
+#[serde(crate = "near_sdk::serde")]
+pub enum VestingTemplate {
+    LinearVesting {
+        #[serde(with = "u64_dec_format")]
+        duration: SecondTimeStamp,
+    },
+    CliffVesting {
+        checkpoints: Vec<TemplateCheckpoint>,
+    },
+}
+
+/// A saved template with its id.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(crate = "near_sdk::serde")]
+pub struct VestingTemplateView {
+    pub id: TemplateId,
+    pub template: VestingTemplate,
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Debug, Serialize, Deserialize, Clone)]
+#[serde(crate = "near_sdk::serde")]
+pub struct TemplateCheckpoint {
+    // seconds after the start time
+    #[serde(with = "u64_dec_format")]
+    pub offset: SecondTimeStamp,
+    // share of the total amount, in units of 1 / TEMPLATE_RATIO_DENOMINATOR
+    pub ratio: u32,
+}
+
+impl VestingTemplate {
+    pub fn assert_valid(&self) {
+        match self {
+            VestingTemplate::LinearVesting { duration } => {
+                assert!(
+                    *duration > 0,
+                    "The duration of template should be greater than 0."
+                )
+            }
+            VestingTemplate::CliffVesting { checkpoints } => {
+                assert!(
+                    !checkpoints.is_empty(),
+                    "The checkpoints of template should not be empty."
+                );
+                assert_eq!(
+                    checkpoints.iter().map(|e| e.ratio as u64).sum::<u64>(),
+                    TEMPLATE_RATIO_DENOMINATOR as u64,
+                    "The sum of checkpoint ratios should be {}.",
+                    TEMPLATE_RATIO_DENOMINATOR
+                );
+            }
+        }
+    }
+
+    /// Expand the template into a concrete vesting create param.
+    pub fn to_create_param(
+        &self,
+        beneficiary: AccountId,
+        start_time: SecondTimeStamp,
+        amount: Balance,
+    ) -> VestingCreateParam {
+        match self {
+            VestingTemplate::LinearVesting { duration } => VestingCreateParam::LinearVesting {
+                beneficiary,
+                start_time,
+                end_time: start_time + duration,
+                total_vesting_amount: amount,
+            },
+            VestingTemplate::CliffVesting { checkpoints } => {
+                let mut remaining_amount = amount;
+                let mut time_cliff_list = checkpoints
+                    .iter()
+                    .map(|e| {
+                        let checkpoint_amount = (U256::from(amount) * U256::from(e.ratio)
+                            / U256::from(TEMPLATE_RATIO_DENOMINATOR))
+                        .as_u128();
+                        remaining_amount -= checkpoint_amount;
+                        CliffVestingCheckpoint {
+                            time: start_time + e.offset,
+                            amount: checkpoint_amount,
+                        }
+                    })
+                    .collect_vec();
+                // the rounding remainder is released at the last checkpoint
+                time_cliff_list.last_mut().unwrap().amount += remaining_amount;
+                VestingCreateParam::CliffVesting {
+                    beneficiary,
+                    time_cliff_list,
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use near_sdk::test_utils::test_env::bob;
+
+    #[test]
+    fn test_cliff_template() {
+        let template = VestingTemplate::CliffVesting {
+            checkpoints: vec![
+                TemplateCheckpoint {
+                    offset: 100,
+                    ratio: 2500,
+                },
+                TemplateCheckpoint {
+                    offset: 200,
+                    ratio: 2500,
+                },
+                TemplateCheckpoint {
+                    offset: 300,
+                    ratio: 5000,
+                },
+            ],
+        };
+        template.assert_valid();
+
+        match template.to_create_param(bob(), 1000, 101) {
+            VestingCreateParam::CliffVesting {
+                time_cliff_list, ..
+            } => assert_eq!(
+                time_cliff_list,
+                vec![
+                    CliffVestingCheckpoint {
+                        time: 1100,
+                        amount: 25
+                    },
+                    CliffVestingCheckpoint {
+                        time: 1200,
+                        amount: 25
+                    },
+                    CliffVestingCheckpoint {
+                        time: 1300,
+                        amount: 51
+                    },
+                ]
+            ),
+            VestingCreateParam::LinearVesting { .. } => panic!("should be cliff vesting"),
+        }
+    }
+}
@@ -0,0 +1,244 @@
+use crate::events::{EventEmit, UserAction, VestingEvent};
+use crate::interfaces::Timelock;
+use crate::types::{ScheduledActionId, SecondTimeStamp};
+use crate::vesting::VestingCreateParam;
+use crate::*;
+use near_contract_standards::upgrade::Ownable;
+use near_sdk::json_types::U64;
+
+/// Owner actions which can be delayed by the timelock.
+#[derive(BorshSerialize, BorshDeserialize, Debug, Serialize, Deserialize, Clone)]
+#[serde(crate = "near_sdk::serde")]
+#[serde(tag = "action_type", content = "data")]
+#[serde(rename_all = "snake_case")]
+pub enum SensitiveAction {
+    TerminateVesting {
+        vesting_id: VestingId,
+    },
+    AmendVesting {
+        vesting_id: VestingId,
+        param: VestingCreateParam,
+    },
+    Withdraw {
+        receiver_id: AccountId,
+        amount: U128,
+    },
+    SetOwner {
+        owner: AccountId,
+    },
+    SetTimelockDelay {
+        delay: U64,
+    },
+    SetApprovers {
+        approvers: Vec<AccountId>,
+        threshold: u32,
+    },
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Debug, Serialize, Deserialize, Clone)]
+#[serde(crate = "near_sdk::serde")]
+pub struct ScheduledAction {
+    pub id: ScheduledActionId,
+    pub action: SensitiveAction,
+    pub proposer: AccountId,
+    #[serde(with = "u64_dec_format")]
+    pub schedule_time: SecondTimeStamp,
+    #[serde(with = "u64_dec_format")]
+    pub executable_time: SecondTimeStamp,
+}
+
+#[near_bindgen]
+impl Timelock for TokenVestingContract {
+    #[payable]
+    fn schedule_action(&mut self, action: SensitiveAction) -> ScheduledActionId {
+        self.assert_can_perform(&action);
+        assert!(
+            !(self.is_multisig_enabled() && action.requires_approval()),
+            "This action should be proposed to approvers."
+        );
+        self.assert_valid_action(&action);
+        let prev_storage = env::storage_usage();
+
+        let id = self.internal_schedule_action(action, env::predecessor_account_id());
+        self.internal_check_storage(prev_storage);
+        id
+    }
+
+    fn cancel_scheduled_action(&mut self, action_id: ScheduledActionId) {
+        let scheduled_action = self
+            .scheduled_actions
+            .get(&action_id)
+            .unwrap_or_else(|| panic!("No such scheduled action id: #{}.", action_id.0));
+        self.assert_can_perform_scheduled(&scheduled_action.action);
+
+        self.scheduled_actions.remove(&action_id);
+        UserAction::CancelScheduledAction {
+            action_id: &action_id,
+        }
+        .emit();
+    }
+
+    #[payable]
+    fn execute_scheduled_action(&mut self, action_id: ScheduledActionId) {
+        let scheduled_action = self
+            .scheduled_actions
+            .get(&action_id)
+            .unwrap_or_else(|| panic!("No such scheduled action id: #{}.", action_id.0));
+        self.assert_can_perform_scheduled(&scheduled_action.action);
+        assert!(
+            get_block_second_time() >= scheduled_action.executable_time,
+            "The scheduled action #{} is not executable until {}.",
+            action_id.0,
+            scheduled_action.executable_time
+        );
+        let prev_storage = env::storage_usage();
+
+        self.scheduled_actions.remove(&action_id);
+        self.internal_execute_sensitive_action(scheduled_action.action);
+        self.internal_check_storage(prev_storage);
+
+        UserAction::ExecuteScheduledAction {
+            action_id: &action_id,
+        }
+        .emit();
+    }
+
+    fn set_timelock_delay(&mut self, delay: U64) {
+        self.assert_owner();
+        self.assert_no_timelock();
+        self.timelock_delay = delay.0;
+    }
+}
+
+impl TokenVestingContract {
+    /// Check that the predecessor is allowed to schedule, cancel or execute the action.
+    pub(crate) fn assert_can_perform(&self, action: &SensitiveAction) {
+        match action {
+            SensitiveAction::TerminateVesting { .. } => self.assert_role(Role::Terminator),
+            SensitiveAction::AmendVesting { .. } => self.assert_role(Role::Admin),
+            SensitiveAction::Withdraw { .. }
+            | SensitiveAction::SetOwner { .. }
+            | SensitiveAction::SetTimelockDelay { .. }
+            | SensitiveAction::SetApprovers { .. } => self.assert_owner(),
+        }
+    }
+
+    /// Reject the action when it is scheduled or proposed, if it would fail on execution
+    /// with the current state.
+    pub(crate) fn assert_valid_action(&self, action: &SensitiveAction) {
+        match action {
+            SensitiveAction::TerminateVesting { vesting_id } => assert!(
+                self.vestings.get(vesting_id).is_some(),
+                "No such vesting id: #{}.",
+                vesting_id.0
+            ),
+            SensitiveAction::AmendVesting { vesting_id, param } => {
+                self.internal_get_amended_vesting(vesting_id, param.clone());
+            }
+            _ => {}
+        }
+    }
+
+    /// Actions approved by approvers are cancelled and executed by approvers.
+    fn assert_can_perform_scheduled(&self, action: &SensitiveAction) {
+        if self.is_multisig_enabled() && action.requires_approval() {
+            self.assert_approver();
+        } else {
+            self.assert_can_perform(action);
+        }
+    }
+
+    pub(crate) fn internal_schedule_action(
+        &mut self,
+        action: SensitiveAction,
+        proposer: AccountId,
+    ) -> ScheduledActionId {
+        let id = self.internal_assign_id();
+        let schedule_time = get_block_second_time();
+        let scheduled_action = ScheduledAction {
+            id,
+            action,
+            proposer,
+            schedule_time,
+            executable_time: schedule_time + self.timelock_delay,
+        };
+        self.scheduled_actions.insert(&id, &scheduled_action);
+
+        UserAction::ScheduleAction {
+            action_id: &id,
+            action: &scheduled_action.action,
+            executable_time: &U64(scheduled_action.executable_time),
+        }
+        .emit();
+        id
+    }
+
+    /// Sensitive actions can only be called directly when the timelock is disabled.
+    pub(crate) fn assert_no_timelock(&self) {
+        assert_eq!(
+            self.timelock_delay, 0,
+            "This action should be scheduled through the timelock."
+        );
+    }
+
+    pub(crate) fn internal_execute_sensitive_action(&mut self, action: SensitiveAction) {
+        match action {
+            SensitiveAction::TerminateVesting { vesting_id } => {
+                self.internal_terminate_vesting(&vesting_id);
+                UserAction::TerminateVesting {
+                    vesting_id: &vesting_id,
+                }
+                .emit();
+            }
+            SensitiveAction::AmendVesting { vesting_id, param } => {
+                self.internal_amend_vesting(&vesting_id, param);
+                VestingEvent::UpdateVesting {
+                    vesting: &self.internal_get_vesting(&vesting_id).unwrap_or_else(|| {
+                        panic!("Failed to get vesting by id: {}.", vesting_id.0)
+                    }),
+                }
+                .emit();
+                UserAction::AmendVesting {
+                    vesting_id: &vesting_id,
+                }
+                .emit();
+            }
+            SensitiveAction::Withdraw {
+                receiver_id,
+                amount,
+            } => self.internal_withdraw(&receiver_id, amount.0),
+            SensitiveAction::SetOwner { owner } => self.owner = owner,
+            SensitiveAction::SetTimelockDelay { delay } => self.timelock_delay = delay.0,
+            SensitiveAction::SetApprovers {
+                approvers,
+                threshold,
+            } => self.internal_set_approvers(approvers, threshold),
+        }
+    }
+
+    pub(crate) fn internal_withdraw(&mut self, receiver_id: &AccountId, amount: Balance) {
+        assert!(
+            self.vestings.is_empty(),
+            "Failed to withdraw because there are still vestings in this contract."
+        );
+        assert_eq!(
+            self.total_pending_balance, 0,
+            "Failed to withdraw because there are still pending balances in this contract."
+        );
+        assert_eq!(
+            self.total_unmaterialized_airdrop_amount, 0,
+            "Failed to withdraw because there are still airdrop vestings to be claimed in this contract."
+        );
+        let transfer_id = self.internal_assign_id();
+
+        UserAction::Withdraw {
+            transfer_id: &transfer_id,
+            receiver_id,
+            token_id: &self.token_id,
+            amount: &U128(amount),
+        }
+        .emit();
+
+        self.internal_send_tokens(receiver_id, &self.token_id.clone(), amount, transfer_id);
+    }
+}
@@ -9,3 +9,8 @@ construct_uint! {
 pub type SecondTimeStamp = u64;
 pub type VestingId = U64;
 pub type TransferId = U64;
+pub type ScheduledActionId = U64;
+pub type ProposalId = U64;
+pub type AirdropId = U64;
+pub type TemplateId = String;
+pub type PoolId = String;
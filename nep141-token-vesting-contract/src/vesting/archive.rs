@@ -0,0 +1,129 @@
+use crate::events::{EventEmit, VestingEvent};
+use crate::types::SecondTimeStamp;
+use crate::utils::get_block_second_time;
+use crate::vesting::traits::{Beneficiary, Frozen, VestingTokenInfoTrait};
+use crate::*;
+use near_sdk::collections::Vector;
+
+#[derive(
+    BorshSerialize, BorshDeserialize, Debug, Serialize, Deserialize, Clone, Copy, PartialEq,
+)]
+#[serde(crate = "near_sdk::serde")]
+pub enum VestingStatus {
+    Active,
+    Frozen,
+    Finished,
+    Terminated,
+}
+
+/// A compact summary of a removed vesting, kept if the archive is enabled.
+#[derive(BorshSerialize, BorshDeserialize, Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(crate = "near_sdk::serde")]
+pub struct ArchivedVesting {
+    pub vesting_id: VestingId,
+    pub beneficiary: AccountId,
+    #[serde(with = "u128_dec_format")]
+    pub total_vesting_amount: Balance,
+    #[serde(with = "u128_dec_format")]
+    pub claimed_token_amount: Balance,
+    pub status: VestingStatus,
+    // when the vesting is finished or terminated
+    #[serde(with = "u64_dec_format")]
+    pub end_time: SecondTimeStamp,
+}
+
+impl TokenVestingContract {
+    /// Remove a fully claimed vesting, the vesting should be the claimed state
+    /// as the stored one may not be saved yet.
+    pub(crate) fn internal_finish_vesting(&mut self, vesting: &Vesting) {
+        let vesting_id = vesting.get_vesting_id();
+        // count the last claim before the vesting is removed
+        self.internal_save_vesting(vesting);
+        self.stats.finished_count += 1;
+        let archived_storage = self.internal_archive_vesting(vesting, VestingStatus::Finished);
+        self.internal_remove_vesting(&vesting_id, archived_storage);
+        VestingEvent::FinishVesting {
+            vesting_id: &vesting_id,
+        }
+        .emit();
+    }
+
+    /// Archive the vesting before it is removed, returns the storage taken by the archive
+    /// record and the claim log kept with it, which is paid by the storage deposit of the vesting.
+    pub(crate) fn internal_archive_vesting(
+        &mut self,
+        vesting: &Vesting,
+        status: VestingStatus,
+    ) -> StorageUsage {
+        if !self.is_archive_enabled {
+            return 0;
+        }
+        let prev_storage = env::storage_usage();
+        let vesting_id = vesting.get_vesting_id();
+        let beneficiary = vesting.get_beneficiary();
+        let token_info = vesting.get_vesting_token_info();
+        self.archived_vestings.insert(
+            &vesting_id,
+            &ArchivedVesting {
+                vesting_id,
+                beneficiary: beneficiary.clone(),
+                total_vesting_amount: token_info.total_vesting_amount,
+                claimed_token_amount: token_info.claimed_token_amount,
+                status,
+                end_time: get_block_second_time(),
+            },
+        );
+
+        let mut vesting_ids = self
+            .beneficiary_archived_vestings
+            .get(&beneficiary)
+            .unwrap_or_else(|| {
+                Vector::new(StorageKey::BeneficiaryArchivedVestingsInner {
+                    account_hash: env::sha256_array(beneficiary.as_bytes()),
+                })
+            });
+        vesting_ids.push(&vesting_id);
+        self.beneficiary_archived_vestings
+            .insert(&beneficiary, &vesting_ids);
+        let archive_storage = env::storage_usage() - prev_storage;
+
+        // the claim log is measured by taking it out and putting it back
+        let claim_log_storage = match self.claim_logs.remove(&vesting_id) {
+            Some(records) => {
+                let prev_storage = env::storage_usage();
+                self.claim_logs.insert(&vesting_id, &records);
+                env::storage_usage() - prev_storage
+            }
+            None => 0,
+        };
+        archive_storage + claim_log_storage
+    }
+
+    pub(crate) fn internal_get_vesting_status(
+        &self,
+        vesting_id: &VestingId,
+    ) -> Option<VestingStatus> {
+        match self.internal_get_vesting(vesting_id) {
+            Some(vesting) if vesting.is_frozen() => Some(VestingStatus::Frozen),
+            Some(_) => Some(VestingStatus::Active),
+            None => self.archived_vestings.get(vesting_id).map(|e| e.status),
+        }
+    }
+
+    pub(crate) fn internal_get_archived_vestings(
+        &self,
+        beneficiary: &AccountId,
+        from_index: u32,
+        limit: u32,
+    ) -> Vec<ArchivedVesting> {
+        match self.beneficiary_archived_vestings.get(beneficiary) {
+            Some(vesting_ids) => vesting_ids
+                .iter()
+                .skip(from_index as usize)
+                .take(limit as usize)
+                .filter_map(|e| self.archived_vestings.get(&e))
+                .collect_vec(),
+            None => vec![],
+        }
+    }
+}
@@ -1,6 +1,5 @@
 use super::*;
 use crate::types::SecondTimeStamp;
-use crate::utils::get_block_second_time;
 use crate::vesting::traits::{Beneficiary, Finish, VestingAmount, VestingTokenInfoTrait};
 use crate::vesting::VestingTokenInfo;
 use near_sdk::{AccountId, Balance};
@@ -30,14 +29,14 @@ pub struct CliffVestingCheckpoint {
 }
 
 impl Finish for TimeCliffVesting {
-    fn is_release_finish(&self) -> bool {
+    fn is_release_finish_at(&self, time: SecondTimeStamp) -> bool {
         let max_time = self
             .time_cliff_list
             .iter()
             .map(|e| e.time)
             .max()
             .unwrap_or(0);
-        return max_time <= get_block_second_time();
+        max_time <= time
     }
 }
 
@@ -82,17 +81,10 @@ impl VestingTokenInfoTrait for TimeCliffVesting {
 }
 
 impl VestingAmount for TimeCliffVesting {
-    fn get_unreleased_amount(&self) -> Balance {
-        let block_second_time = get_block_second_time();
+    fn get_unreleased_amount_at(&self, time: SecondTimeStamp) -> Balance {
         self.time_cliff_list
             .iter()
-            .map(|e| {
-                if e.time > block_second_time {
-                    e.amount
-                } else {
-                    0
-                }
-            })
+            .map(|e| if e.time > time { e.amount } else { 0 })
             .sum()
     }
 }
@@ -130,4 +122,52 @@ mod tests {
         vesting.claim();
         assert_eq!(vesting.get_claimable_amount(), 0);
     }
+    #[test]
+    fn test_cliff_claim_amount() {
+        let mut context = VMContextBuilder::new();
+        testing_env!(context.block_timestamp(2 * 1000_000_000).build());
+
+        let mut vesting = TimeCliffVesting {
+            id: U64(1),
+            beneficiary: bob(),
+            time_cliff_list: vec![
+                CliffVestingCheckpoint { time: 1, amount: 5 },
+                CliffVestingCheckpoint { time: 3, amount: 5 },
+            ],
+            vesting_token_info: VestingTokenInfo {
+                claimed_token_amount: 0,
+                total_vesting_amount: 10,
+            },
+            is_frozen: false,
+            create_time: get_block_second_time(),
+        };
+        assert_eq!(vesting.claim_amount(2), 2);
+        assert_eq!(vesting.get_vesting_token_info().claimed_token_amount, 2);
+        assert_eq!(vesting.get_claimable_amount(), 3);
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "The amount to claim should be less or eq than the claimable amount 5."
+    )]
+    fn test_cliff_claim_amount_exceeded() {
+        let mut context = VMContextBuilder::new();
+        testing_env!(context.block_timestamp(2 * 1000_000_000).build());
+
+        let mut vesting = TimeCliffVesting {
+            id: U64(1),
+            beneficiary: bob(),
+            time_cliff_list: vec![
+                CliffVestingCheckpoint { time: 1, amount: 5 },
+                CliffVestingCheckpoint { time: 3, amount: 5 },
+            ],
+            vesting_token_info: VestingTokenInfo {
+                claimed_token_amount: 0,
+                total_vesting_amount: 10,
+            },
+            is_frozen: false,
+            create_time: get_block_second_time(),
+        };
+        vesting.claim_amount(6);
+    }
 }
@@ -0,0 +1,157 @@
+use crate::types::SecondTimeStamp;
+use crate::vesting::traits::{Frozen, NaturalTime};
+use crate::*;
+use near_sdk::json_types::U64;
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+#[serde(crate = "near_sdk::serde")]
+#[serde(rename_all = "snake_case")]
+pub enum VestingType {
+    Linear,
+    Cliff,
+}
+
+/// Conditions a vesting should match, a condition is ignored if it is not set.
+/// The time ranges are inclusive.
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+#[serde(crate = "near_sdk::serde")]
+pub struct VestingFilter {
+    pub vesting_type: Option<VestingType>,
+    pub is_frozen: Option<bool>,
+    pub min_create_time: Option<U64>,
+    pub max_create_time: Option<U64>,
+    pub min_end_time: Option<U64>,
+    pub max_end_time: Option<U64>,
+}
+
+fn is_in_range(time: SecondTimeStamp, min_time: Option<U64>, max_time: Option<U64>) -> bool {
+    min_time.map_or(true, |e| e.0 <= time) && max_time.map_or(true, |e| time <= e.0)
+}
+
+impl Vesting {
+    pub fn get_vesting_type(&self) -> VestingType {
+        match self {
+            Vesting::NaturalTimeLinearVesting(_) => VestingType::Linear,
+            Vesting::TimeCliffVesting(_) => VestingType::Cliff,
+        }
+    }
+
+    /// The time when all tokens are released, the last checkpoint of a cliff vesting.
+    pub fn get_end_time(&self) -> SecondTimeStamp {
+        match self {
+            Vesting::NaturalTimeLinearVesting(linear) => linear.get_end_time(),
+            Vesting::TimeCliffVesting(cliff) => cliff
+                .time_cliff_list
+                .iter()
+                .map(|e| e.time)
+                .max()
+                .unwrap_or(0),
+        }
+    }
+}
+
+impl VestingFilter {
+    pub fn is_match(&self, vesting: &Vesting) -> bool {
+        self.vesting_type
+            .map_or(true, |e| e == vesting.get_vesting_type())
+            && self.is_frozen.map_or(true, |e| e == vesting.is_frozen())
+            && is_in_range(
+                vesting.get_create_time(),
+                self.min_create_time,
+                self.max_create_time,
+            )
+            && is_in_range(vesting.get_end_time(), self.min_end_time, self.max_end_time)
+    }
+}
+
+impl TokenVestingContract {
+    /// Get a page of vestings, optionally of a beneficiary. The page is taken before
+    /// filtering, so that the next page starts at from_index + limit.
+    pub(crate) fn internal_get_vestings(
+        &self,
+        from_index: u32,
+        limit: u32,
+        beneficiary: Option<&AccountId>,
+        filter: Option<&VestingFilter>,
+    ) -> Vec<Vesting> {
+        let vestings = match beneficiary {
+            Some(beneficiary) => self
+                .internal_get_beneficiary_vesting_ids(beneficiary)
+                .iter()
+                .skip(from_index as usize)
+                .take(limit as usize)
+                .filter_map(|e| self.vestings.get(e))
+                .collect_vec(),
+            None => self
+                .vestings
+                .values()
+                .skip(from_index as usize)
+                .take(limit as usize)
+                .collect_vec(),
+        };
+        match filter {
+            Some(filter) => vestings
+                .into_iter()
+                .filter(|e| filter.is_match(e))
+                .collect_vec(),
+            None => vestings,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::vesting::cliff::{CliffVestingCheckpoint, TimeCliffVesting};
+    use crate::vesting::VestingTokenInfo;
+    use near_sdk::test_utils::test_env::bob;
+
+    fn cliff_vesting() -> Vesting {
+        Vesting::TimeCliffVesting(TimeCliffVesting {
+            id: U64(1),
+            beneficiary: bob(),
+            time_cliff_list: vec![
+                CliffVestingCheckpoint {
+                    time: 300,
+                    amount: 20,
+                },
+                CliffVestingCheckpoint {
+                    time: 100,
+                    amount: 10,
+                },
+            ],
+            vesting_token_info: VestingTokenInfo {
+                claimed_token_amount: 0,
+                total_vesting_amount: 30,
+            },
+            is_frozen: false,
+            create_time: 50,
+        })
+    }
+
+    #[test]
+    fn test_filter() {
+        let vesting = cliff_vesting();
+        assert_eq!(vesting.get_end_time(), 300);
+        assert!(VestingFilter::default().is_match(&vesting));
+        assert!(VestingFilter {
+            vesting_type: Some(VestingType::Cliff),
+            is_frozen: Some(false),
+            min_create_time: Some(U64(50)),
+            min_end_time: Some(U64(300)),
+            max_end_time: Some(U64(300)),
+            ..Default::default()
+        }
+        .is_match(&vesting));
+        assert!(!VestingFilter {
+            vesting_type: Some(VestingType::Linear),
+            ..Default::default()
+        }
+        .is_match(&vesting));
+        assert!(!VestingFilter {
+            max_end_time: Some(U64(299)),
+            ..Default::default()
+        }
+        .is_match(&vesting));
+    }
+}
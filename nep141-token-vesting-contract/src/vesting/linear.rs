@@ -24,8 +24,8 @@ pub struct NaturalTimeLinearVesting {
 impl NaturalTimeLinearVesting {}
 
 impl Finish for NaturalTimeLinearVesting {
-    fn is_release_finish(&self) -> bool {
-        self.end_time <= get_block_second_time()
+    fn is_release_finish_at(&self, time: SecondTimeStamp) -> bool {
+        self.end_time <= time
     }
 }
 
@@ -0,0 +1,74 @@
+use super::*;
+use near_sdk::json_types::Base64VecU8;
+
+/// Off-chain description of a vesting, e.g. the grant agreement it belongs to.
+#[derive(BorshSerialize, BorshDeserialize, Debug, Serialize, Deserialize, Clone, Default)]
+#[serde(crate = "near_sdk::serde")]
+pub struct VestingMetadata {
+    pub title: Option<String>,
+    pub memo: Option<String>,
+    // URL to an off-chain document, e.g. the grant agreement
+    pub reference: Option<String>,
+    // hash of the document at the reference
+    pub reference_hash: Option<Base64VecU8>,
+    // category tag, e.g. "team" or "advisor"
+    pub tag: Option<String>,
+}
+
+impl TokenVestingContract {
+    /// Set or remove the metadata of a vesting, and move the vesting in the tag index.
+    pub(crate) fn internal_set_vesting_metadata(
+        &mut self,
+        vesting_id: &VestingId,
+        metadata: Option<&VestingMetadata>,
+    ) {
+        let old_metadata = match metadata {
+            Some(metadata) => self.vesting_metadata.insert(vesting_id, metadata),
+            None => self.vesting_metadata.remove(vesting_id),
+        };
+        let old_tag = old_metadata.and_then(|e| e.tag);
+        let new_tag = metadata.and_then(|e| e.tag.clone());
+        if old_tag == new_tag {
+            return;
+        }
+
+        if let Some(tag) = old_tag {
+            if let Some(mut vesting_ids) = self.tag_vestings.get(&tag) {
+                vesting_ids.remove(vesting_id);
+                // drop the empty set to release its storage
+                if vesting_ids.is_empty() {
+                    self.tag_vestings.remove(&tag);
+                } else {
+                    self.tag_vestings.insert(&tag, &vesting_ids);
+                }
+            }
+        }
+        if let Some(tag) = new_tag {
+            let mut vesting_ids = self.tag_vestings.get(&tag).unwrap_or_else(|| {
+                UnorderedSet::new(StorageKey::TagVestingsInner {
+                    tag_hash: env::sha256_array(tag.as_bytes()),
+                })
+            });
+            vesting_ids.insert(vesting_id);
+            self.tag_vestings.insert(&tag, &vesting_ids);
+        }
+    }
+
+    pub(crate) fn internal_get_vestings_by_tag(
+        &self,
+        tag: &String,
+        from_index: u32,
+        limit: u32,
+    ) -> Vec<Vesting> {
+        match self.tag_vestings.get(tag) {
+            Some(vesting_ids) => vesting_ids
+                .as_vector()
+                .iter()
+                .skip(from_index as usize)
+                .take(limit as usize)
+                .filter_map(|e| self.vestings.get(&e))
+                .collect_vec(),
+            None => vec![],
+        }
+    }
+}
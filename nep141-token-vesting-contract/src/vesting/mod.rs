@@ -1,21 +1,27 @@
-use near_contract_standards::upgrade::Ownable;
 use near_sdk::json_types::U64;
 use near_sdk::{AccountId, Balance};
 use std::cmp::min;
 
 use crate::events::{EventEmit, UserAction, VestingEvent};
-use crate::types::{SecondTimeStamp, U256};
+use crate::types::{PoolId, SecondTimeStamp, U256};
 use crate::utils::get_block_second_time;
+use crate::vesting::archive::VestingStatus;
 use crate::vesting::cliff::{CliffVestingCheckpoint, TimeCliffVesting};
 use crate::vesting::linear::NaturalTimeLinearVesting;
+use crate::vesting::metadata::VestingMetadata;
 use crate::vesting::traits::{
     Beneficiary, Claimable, Finish, Frozen, NaturalTime, VestingAmount, VestingTokenInfoTrait,
 };
 use crate::*;
 
+pub mod archive;
 pub mod cliff;
+pub mod filter;
 pub mod linear;
+pub mod metadata;
+pub mod timeline;
 pub mod traits;
+pub mod view;
 
 #[derive(BorshSerialize, BorshDeserialize, Debug, Serialize, Deserialize, Clone)]
 #[serde(crate = "near_sdk::serde")]
@@ -31,8 +37,11 @@ pub enum Vesting {
 pub enum VestingCreateParam {
     LinearVesting {
         beneficiary: AccountId,
+        #[serde(with = "u64_dec_format")]
         start_time: SecondTimeStamp,
+        #[serde(with = "u64_dec_format")]
         end_time: SecondTimeStamp,
+        #[serde(with = "u128_dec_format")]
         total_vesting_amount: Balance,
     },
     CliffVesting {
@@ -41,6 +50,24 @@ pub enum VestingCreateParam {
     },
 }
 
+/// Selects the vestings affected by a bulk owner action.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(crate = "near_sdk::serde")]
+#[serde(rename_all = "snake_case")]
+pub enum VestingSelector {
+    Beneficiary(AccountId),
+    VestingIds(Vec<VestingId>),
+}
+
+/// The vestings affected by a page of a bulk owner action.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(crate = "near_sdk::serde")]
+pub struct BulkActionResult {
+    pub vesting_ids: Vec<VestingId>,
+    // None if it is the last page
+    pub next_index: Option<u32>,
+}
+
 #[derive(BorshSerialize, BorshDeserialize, Debug, Serialize, Deserialize, Clone)]
 #[serde(crate = "near_sdk::serde")]
 pub struct VestingTokenInfo {
@@ -53,12 +80,12 @@ pub struct VestingTokenInfo {
 }
 
 impl<T: NaturalTime + VestingTokenInfoTrait> VestingAmount for T {
-    fn get_unreleased_amount(&self) -> Balance {
+    fn get_unreleased_amount_at(&self, time: SecondTimeStamp) -> Balance {
         let period = self.get_period();
-        let mut remain_time = if self.get_end_time() <= get_block_second_time() {
+        let mut remain_time = if self.get_end_time() <= time {
             0
         } else {
-            self.get_end_time() - get_block_second_time()
+            self.get_end_time() - time
         };
         remain_time = min(remain_time, period);
         // unreleased_amount / remain_time = total_vesting / period
@@ -71,16 +98,23 @@ impl<T: NaturalTime + VestingTokenInfoTrait> VestingAmount for T {
 
 impl<T: VestingAmount + VestingTokenInfoTrait + Frozen> Claimable for T {
     fn claim(&mut self) -> Balance {
+        self.claim_amount(self.get_claimable_amount())
+    }
+
+    fn claim_amount(&mut self, amount: Balance) -> Balance {
         assert!(
             !self.is_frozen(),
             "Failed to claim because this vesting is frozen."
         );
         let claimable_amount = self.get_claimable_amount();
-
-        self.set_claimed_token_amount(
-            self.get_vesting_token_info().claimed_token_amount + claimable_amount,
+        assert!(
+            amount <= claimable_amount,
+            "The amount to claim should be less or eq than the claimable amount {}.",
+            claimable_amount
         );
-        claimable_amount
+
+        self.set_claimed_token_amount(self.get_vesting_token_info().claimed_token_amount + amount);
+        amount
     }
 }
 
@@ -140,19 +174,19 @@ impl Beneficiary for Vesting {
 }
 
 impl VestingAmount for Vesting {
-    fn get_unreleased_amount(&self) -> Balance {
+    fn get_unreleased_amount_at(&self, time: SecondTimeStamp) -> Balance {
         match self {
-            Vesting::NaturalTimeLinearVesting(linear) => linear.get_unreleased_amount(),
-            Vesting::TimeCliffVesting(cliff) => cliff.get_unreleased_amount(),
+            Vesting::NaturalTimeLinearVesting(linear) => linear.get_unreleased_amount_at(time),
+            Vesting::TimeCliffVesting(cliff) => cliff.get_unreleased_amount_at(time),
         }
     }
 }
 
 impl Finish for Vesting {
-    fn is_release_finish(&self) -> bool {
+    fn is_release_finish_at(&self, time: SecondTimeStamp) -> bool {
         match self {
-            Vesting::NaturalTimeLinearVesting(linear) => linear.is_release_finish(),
-            Vesting::TimeCliffVesting(cliff) => cliff.is_release_finish(),
+            Vesting::NaturalTimeLinearVesting(linear) => linear.is_release_finish_at(time),
+            Vesting::TimeCliffVesting(cliff) => cliff.is_release_finish_at(time),
         }
     }
 }
@@ -165,6 +199,20 @@ impl Vesting {
         }
     }
 
+    pub fn get_create_time(&self) -> SecondTimeStamp {
+        match self {
+            Vesting::NaturalTimeLinearVesting(linear) => linear.create_time,
+            Vesting::TimeCliffVesting(cliff) => cliff.create_time,
+        }
+    }
+
+    pub fn set_create_time(&mut self, create_time: SecondTimeStamp) {
+        match self {
+            Vesting::NaturalTimeLinearVesting(linear) => linear.create_time = create_time,
+            Vesting::TimeCliffVesting(cliff) => cliff.create_time = create_time,
+        }
+    }
+
     pub fn new(id: VestingId, param: VestingCreateParam) -> Self {
         match param {
             VestingCreateParam::LinearVesting {
@@ -217,16 +265,59 @@ impl Vesting {
 }
 
 impl TokenVestingContract {
-    pub(crate) fn internal_create_vesting(&mut self, param: VestingCreateParam) -> VestingId {
-        self.assert_owner();
-        let id = self.internal_assign_id();
+    pub(crate) fn internal_create_vesting(
+        &mut self,
+        param: VestingCreateParam,
+        pool_id: Option<PoolId>,
+        metadata: Option<VestingMetadata>,
+    ) -> VestingId {
+        self.assert_role(Role::Creator);
+        self.assert_not_paused();
+        let prev_storage = env::storage_usage();
+
+        let id = self.internal_insert_pool_vesting(param, &pool_id, metadata);
+        self.internal_charge_vesting_storage(prev_storage, &[id]);
+        id
+    }
+
+    /// Create all vestings in one call, the storage cost of them is charged once.
+    pub(crate) fn internal_create_vestings(
+        &mut self,
+        params: Vec<VestingCreateParam>,
+        pool_id: Option<PoolId>,
+    ) -> Vec<VestingId> {
+        self.assert_role(Role::Creator);
+        self.assert_not_paused();
+        assert!(!params.is_empty(), "No vesting to create.");
         let prev_storage = env::storage_usage();
 
-        self.vestings.insert(&id, &Vesting::new(id.clone(), param));
-        self.internal_check_storage(prev_storage);
+        let ids = params
+            .into_iter()
+            .map(|param| self.internal_insert_pool_vesting(param, &pool_id, None))
+            .collect_vec();
+        self.internal_charge_vesting_storage(prev_storage, &ids);
+        ids
+    }
+
+    pub(crate) fn internal_insert_vesting(
+        &mut self,
+        param: VestingCreateParam,
+        metadata: Option<VestingMetadata>,
+    ) -> VestingId {
+        self.assert_migrated();
+        let id = self.internal_assign_id();
+        let vesting = Vesting::new(id, param);
+
+        self.vestings.insert(&id, &vesting);
+        self.internal_update_stats(None, Some(&vesting));
+        self.internal_index_vesting(&vesting.get_beneficiary(), &id);
+        if let Some(metadata) = &metadata {
+            self.internal_set_vesting_metadata(&id, Some(metadata));
+        }
         VestingEvent::CreateVesting {
-            vesting: &self.internal_get_vesting(&id).unwrap(),
-            token_id: &self.token_id.clone(),
+            vesting: &vesting,
+            token_id: &self.token_id,
+            metadata: &metadata,
         }
         .emit();
         UserAction::CreateVesting { vesting_id: &id }.emit();
@@ -238,8 +329,188 @@ impl TokenVestingContract {
         return U64(self.uuid);
     }
 
-    pub(crate) fn internal_remove_vesting(&mut self, vesting_id: &VestingId) {
-        self.vestings.remove(&vesting_id);
+    /// Remove the vesting and refund its storage, except the archived storage
+    /// which is kept after the removal.
+    pub(crate) fn internal_remove_vesting(
+        &mut self,
+        vesting_id: &VestingId,
+        archived_storage: StorageUsage,
+    ) {
+        self.assert_migrated();
+        if let Some(vesting) = self.vestings.remove(vesting_id) {
+            self.internal_update_stats(Some(&vesting), None);
+            self.internal_unindex_vesting(&vesting.get_beneficiary(), vesting_id);
+        }
+        self.vesting_pools.remove(vesting_id);
+        self.internal_set_vesting_metadata(vesting_id, None);
+        self.internal_reset_claim_policy(vesting_id);
+        self.auto_distributions.remove(vesting_id);
+        self.internal_refund_vesting_storage(vesting_id, archived_storage);
+        if !self.is_archive_enabled {
+            self.claim_logs.remove(vesting_id);
+        }
+    }
+
+    /// Remove the vesting, the amount not claimed yet is returned to its pool.
+    pub(crate) fn internal_terminate_vesting(&mut self, vesting_id: &VestingId) {
+        if let (Some(vesting), Some(pool_id)) = (
+            self.internal_get_vesting(vesting_id),
+            self.vesting_pools.get(vesting_id),
+        ) {
+            let token_info = vesting.get_vesting_token_info();
+            self.internal_return_to_pool(
+                &pool_id,
+                token_info.total_vesting_amount - token_info.claimed_token_amount,
+            );
+        }
+        let mut archived_storage = 0;
+        if let Some(vesting) = self.internal_get_vesting(vesting_id) {
+            // count the amount released until now before it is removed
+            self.internal_save_vesting(&vesting);
+            let token_info = vesting.get_vesting_token_info();
+            self.stats.total_clawed_back +=
+                token_info.total_vesting_amount - token_info.claimed_token_amount;
+            self.stats.terminated_count += 1;
+            archived_storage = self.internal_archive_vesting(&vesting, VestingStatus::Terminated);
+        }
+        self.internal_remove_vesting(vesting_id, archived_storage);
+
+        VestingEvent::TerminateVesting { vesting_id }.emit();
+    }
+
+    /// Get a page of existing vesting ids matched by the selector, and whether it is the last page.
+    /// The vestings of a beneficiary are paged from the end of the index like claim_all,
+    /// so that removing them doesn't move the vestings not processed yet.
+    pub(crate) fn internal_select_vesting_ids(
+        &self,
+        selector: &VestingSelector,
+        from_index: u32,
+        limit: u32,
+    ) -> (Vec<VestingId>, bool) {
+        match selector {
+            VestingSelector::Beneficiary(beneficiary) => {
+                self.internal_get_beneficiary_page(beneficiary, from_index, limit)
+            }
+            VestingSelector::VestingIds(vesting_ids) => (
+                vesting_ids
+                    .iter()
+                    .skip(from_index as usize)
+                    .take(limit as usize)
+                    .filter(|e| self.vestings.get(e).is_some())
+                    .cloned()
+                    .collect_vec(),
+                from_index as u64 + limit as u64 >= vesting_ids.len() as u64,
+            ),
+        }
+    }
+
+    /// Build the vesting with the new schedule, keeping its claimed amount, frozen status
+    /// and create time. Panics if the schedule is invalid or the new released amount is less
+    /// than the claimed amount, it is checked again on execution as the beneficiary can claim
+    /// during the timelock delay.
+    pub(crate) fn internal_get_amended_vesting(
+        &self,
+        vesting_id: &VestingId,
+        param: VestingCreateParam,
+    ) -> Vesting {
+        let old_vesting = self
+            .internal_get_vesting(vesting_id)
+            .unwrap_or_else(|| panic!("No such vesting id: #{}.", vesting_id.0));
+
+        let mut vesting = Vesting::new(*vesting_id, param);
+        vesting.set_claimed_token_amount(old_vesting.get_vesting_token_info().claimed_token_amount);
+        vesting.set_create_time(old_vesting.get_create_time());
+        if old_vesting.is_frozen() {
+            vesting.freeze();
+        }
+        assert!(
+            vesting.get_released_amount() >= vesting.get_vesting_token_info().claimed_token_amount,
+            "The released amount of the amended vesting should be greater or eq than the claimed amount."
+        );
+        vesting
+    }
+
+    /// Replace the schedule of a vesting, keeping its claimed amount, frozen status and create time.
+    pub(crate) fn internal_amend_vesting(
+        &mut self,
+        vesting_id: &VestingId,
+        param: VestingCreateParam,
+    ) {
+        let old_vesting = self
+            .internal_get_vesting(vesting_id)
+            .unwrap_or_else(|| panic!("No such vesting id: #{}.", vesting_id.0));
+        let vesting = self.internal_get_amended_vesting(vesting_id, param);
+        if vesting.get_beneficiary() != old_vesting.get_beneficiary() {
+            self.internal_unindex_vesting(&old_vesting.get_beneficiary(), vesting_id);
+            self.internal_index_vesting(&vesting.get_beneficiary(), vesting_id);
+        }
+        // the pool allocation follows the new total amount
+        if let Some(pool_id) = self.vesting_pools.get(vesting_id) {
+            self.internal_return_to_pool(
+                &pool_id,
+                old_vesting.get_vesting_token_info().total_vesting_amount,
+            );
+            self.internal_allocate_from_pool(
+                &pool_id,
+                vesting.get_vesting_token_info().total_vesting_amount,
+            );
+        }
+        self.internal_save_vesting(&vesting);
+    }
+
+    pub(crate) fn internal_index_vesting(
+        &mut self,
+        beneficiary: &AccountId,
+        vesting_id: &VestingId,
+    ) {
+        let mut vesting_ids = self
+            .beneficiary_vestings
+            .get(beneficiary)
+            .unwrap_or_else(|| {
+                self.stats.beneficiary_count += 1;
+                UnorderedSet::new(StorageKey::BeneficiaryVestingsInner {
+                    account_hash: env::sha256_array(beneficiary.as_bytes()),
+                })
+            });
+        vesting_ids.insert(vesting_id);
+        self.beneficiary_vestings.insert(beneficiary, &vesting_ids);
+    }
+
+    pub(crate) fn internal_unindex_vesting(
+        &mut self,
+        beneficiary: &AccountId,
+        vesting_id: &VestingId,
+    ) {
+        if let Some(mut vesting_ids) = self.beneficiary_vestings.get(beneficiary) {
+            vesting_ids.remove(vesting_id);
+            // drop the empty set to release its storage
+            if vesting_ids.is_empty() {
+                self.beneficiary_vestings.remove(beneficiary);
+                self.stats.beneficiary_count -= 1;
+            } else {
+                self.beneficiary_vestings.insert(beneficiary, &vesting_ids);
+            }
+        }
+    }
+
+    pub(crate) fn internal_get_beneficiary_vesting_ids(
+        &self,
+        beneficiary: &AccountId,
+    ) -> Vec<VestingId> {
+        self.beneficiary_vestings
+            .get(beneficiary)
+            .map(|vesting_ids| vesting_ids.to_vec())
+            .unwrap_or_default()
+    }
+
+    pub(crate) fn internal_get_beneficiary_vestings(
+        &self,
+        beneficiary: &AccountId,
+    ) -> Vec<Vesting> {
+        self.internal_get_beneficiary_vesting_ids(beneficiary)
+            .iter()
+            .filter_map(|e| self.vestings.get(e))
+            .collect_vec()
     }
 
     pub(crate) fn internal_get_vesting(&self, vesting_id: &VestingId) -> Option<Vesting> {
@@ -259,6 +530,8 @@ impl TokenVestingContract {
     }
 
     pub(crate) fn internal_save_vesting(&mut self, vesting: &Vesting) {
-        self.vestings.insert(&vesting.get_vesting_id(), &vesting);
+        self.assert_migrated();
+        let old_vesting = self.vestings.insert(&vesting.get_vesting_id(), &vesting);
+        self.internal_update_stats(old_vesting.as_ref(), Some(vesting));
     }
 }
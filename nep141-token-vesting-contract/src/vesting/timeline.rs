@@ -0,0 +1,180 @@
+use crate::constants::MAX_UNLOCK_TIMELINE_POINTS;
+use crate::types::SecondTimeStamp;
+use crate::vesting::traits::{NaturalTime, VestingAmount};
+use crate::*;
+
+/// The amounts of a vesting at a time.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(crate = "near_sdk::serde")]
+pub struct VestingAmounts {
+    #[serde(with = "u64_dec_format")]
+    pub time: SecondTimeStamp,
+    #[serde(with = "u128_dec_format")]
+    pub released_amount: Balance,
+    #[serde(with = "u128_dec_format")]
+    pub unreleased_amount: Balance,
+    #[serde(with = "u128_dec_format")]
+    pub claimable_amount: Balance,
+}
+
+/// The total released amount of a vesting at a time.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(crate = "near_sdk::serde")]
+pub struct UnlockPoint {
+    #[serde(with = "u64_dec_format")]
+    pub time: SecondTimeStamp,
+    #[serde(with = "u128_dec_format")]
+    pub released_amount: Balance,
+}
+
+impl Vesting {
+    pub fn get_amounts_at(&self, time: SecondTimeStamp) -> VestingAmounts {
+        VestingAmounts {
+            time,
+            released_amount: self.get_released_amount_at(time),
+            unreleased_amount: self.get_unreleased_amount_at(time),
+            claimable_amount: self.get_claimable_amount_at(time),
+        }
+    }
+
+    /// The unlock points of a cliff vesting are its checkpoints. A linear vesting releases
+    /// tokens every second, its points are taken every interval from the start to the end time,
+    /// or only the start and end time if there is no interval. The interval is clamped between
+    /// 1 second and the duration of the vesting.
+    pub fn get_unlock_timeline(&self, interval: Option<SecondTimeStamp>) -> Vec<UnlockPoint> {
+        let times = match self {
+            Vesting::NaturalTimeLinearVesting(linear) => {
+                let (start_time, end_time) = (linear.get_start_time(), linear.get_end_time());
+                let duration = end_time - start_time;
+                let interval = interval.unwrap_or(duration).clamp(1, duration);
+                assert!(
+                    duration / interval < MAX_UNLOCK_TIMELINE_POINTS as u64,
+                    "The interval is too small, at most {} points are returned.",
+                    MAX_UNLOCK_TIMELINE_POINTS
+                );
+                // stepping by u64, as an interval cast to usize would wrap on wasm32
+                let mut times =
+                    std::iter::successors(Some(start_time), |e| e.checked_add(interval))
+                        .take_while(|e| *e < end_time)
+                        .collect_vec();
+                times.push(end_time);
+                times
+            }
+            Vesting::TimeCliffVesting(cliff) => cliff
+                .time_cliff_list
+                .iter()
+                .map(|e| e.time)
+                .sorted()
+                .dedup()
+                .collect_vec(),
+        };
+        times
+            .into_iter()
+            .map(|time| UnlockPoint {
+                time,
+                released_amount: self.get_released_amount_at(time),
+            })
+            .collect_vec()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::vesting::cliff::{CliffVestingCheckpoint, TimeCliffVesting};
+    use crate::vesting::linear::NaturalTimeLinearVesting;
+    use crate::vesting::VestingTokenInfo;
+    use near_sdk::json_types::U64;
+    use near_sdk::test_utils::test_env::bob;
+
+    fn linear_vesting() -> Vesting {
+        Vesting::NaturalTimeLinearVesting(NaturalTimeLinearVesting {
+            id: U64(1),
+            beneficiary: bob(),
+            start_time: 1,
+            end_time: 10,
+            vesting_token_info: VestingTokenInfo {
+                claimed_token_amount: 20,
+                total_vesting_amount: 100,
+            },
+            is_frozen: false,
+            create_time: 0,
+        })
+    }
+
+    #[test]
+    fn test_amounts_at() {
+        let vesting = linear_vesting();
+        assert_eq!(
+            vesting.get_amounts_at(5),
+            VestingAmounts {
+                time: 5,
+                released_amount: 50,
+                unreleased_amount: 50,
+                claimable_amount: 30,
+            }
+        );
+        // the released amount is less than the claimed amount before the vesting starts
+        assert_eq!(vesting.get_amounts_at(0).claimable_amount, 0);
+    }
+
+    #[test]
+    fn test_linear_unlock_timeline() {
+        let vesting = linear_vesting();
+        assert_eq!(
+            vesting
+                .get_unlock_timeline(Some(4))
+                .iter()
+                .map(|e| (e.time, e.released_amount))
+                .collect_vec(),
+            vec![(1, 10), (5, 50), (9, 90), (10, 100)]
+        );
+        assert_eq!(vesting.get_unlock_timeline(None).len(), 2);
+    }
+
+    #[test]
+    fn test_huge_unlock_timeline_interval() {
+        let vesting = linear_vesting();
+        for interval in [0, 1 << 32, u64::MAX] {
+            assert_eq!(
+                vesting
+                    .get_unlock_timeline(Some(interval))
+                    .iter()
+                    .map(|e| e.time)
+                    .collect_vec(),
+                if interval == 0 {
+                    (1..=10).collect_vec()
+                } else {
+                    vec![1, 10]
+                }
+            );
+        }
+    }
+
+    #[test]
+    fn test_cliff_unlock_timeline() {
+        let vesting = Vesting::TimeCliffVesting(TimeCliffVesting {
+            id: U64(1),
+            beneficiary: bob(),
+            time_cliff_list: vec![
+                CliffVestingCheckpoint { time: 3, amount: 2 },
+                CliffVestingCheckpoint { time: 1, amount: 1 },
+                CliffVestingCheckpoint { time: 3, amount: 3 },
+            ],
+            vesting_token_info: VestingTokenInfo {
+                claimed_token_amount: 0,
+                total_vesting_amount: 6,
+            },
+            is_frozen: false,
+            create_time: 0,
+        });
+        assert_eq!(
+            vesting
+                .get_unlock_timeline(None)
+                .iter()
+                .map(|e| (e.time, e.released_amount))
+                .collect_vec(),
+            vec![(1, 1), (3, 6)]
+        );
+    }
+}
@@ -1,4 +1,5 @@
 use crate::types::SecondTimeStamp;
+use crate::utils::get_block_second_time;
 use crate::vesting::VestingTokenInfo;
 use near_sdk::{AccountId, Balance};
 
@@ -27,17 +28,31 @@ pub trait VestingTokenInfoTrait {
     fn set_claimed_token_amount(&mut self, amount: Balance);
 }
 
+/// The amounts are calculated at an explicit time, the methods without a time
+/// use the current block time.
 pub trait VestingAmount: VestingTokenInfoTrait {
     //  released amount logically
-    fn get_released_amount(&self) -> Balance {
+    fn get_released_amount_at(&self, time: SecondTimeStamp) -> Balance {
         let total_amount = self.get_vesting_token_info().total_vesting_amount;
+        let unreleased_amount = self.get_unreleased_amount_at(time);
         assert!(
-            total_amount >= self.get_unreleased_amount(),
+            total_amount >= unreleased_amount,
             "total amount should ge released amount."
         );
-        total_amount - self.get_unreleased_amount()
+        total_amount - unreleased_amount
+    }
+    fn get_unreleased_amount_at(&self, time: SecondTimeStamp) -> Balance;
+    // the claimed amount is the current one, as nothing can be claimed in the future yet
+    fn get_claimable_amount_at(&self, time: SecondTimeStamp) -> Balance {
+        self.get_released_amount_at(time)
+            .saturating_sub(self.get_vesting_token_info().claimed_token_amount)
+    }
+    fn get_released_amount(&self) -> Balance {
+        self.get_released_amount_at(get_block_second_time())
+    }
+    fn get_unreleased_amount(&self) -> Balance {
+        self.get_unreleased_amount_at(get_block_second_time())
     }
-    fn get_unreleased_amount(&self) -> Balance;
     fn get_claimable_amount(&self) -> Balance {
         self.get_released_amount() - self.get_vesting_token_info().claimed_token_amount
     }
@@ -45,10 +60,14 @@ pub trait VestingAmount: VestingTokenInfoTrait {
 
 pub trait Claimable {
     fn claim(&mut self) -> Balance;
+    fn claim_amount(&mut self, amount: Balance) -> Balance;
 }
 
 pub trait Finish: VestingTokenInfoTrait {
-    fn is_release_finish(&self) -> bool;
+    fn is_release_finish_at(&self, time: SecondTimeStamp) -> bool;
+    fn is_release_finish(&self) -> bool {
+        self.is_release_finish_at(get_block_second_time())
+    }
     fn is_vesting_finish(&self) -> bool {
         self.is_release_finish()
             && self.get_vesting_token_info().total_vesting_amount
@@ -0,0 +1,151 @@
+use crate::types::SecondTimeStamp;
+use crate::utils::get_block_second_time;
+use crate::vesting::archive::VestingStatus;
+use crate::vesting::filter::VestingType;
+use crate::vesting::traits::{
+    Beneficiary, Frozen, NaturalTime, VestingAmount, VestingTokenInfoTrait,
+};
+use crate::*;
+use near_sdk::json_types::U64;
+
+/// A flat summary of a vesting with the amounts computed at a time,
+/// the same for all vesting types.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(crate = "near_sdk::serde")]
+pub struct VestingView {
+    pub vesting_id: VestingId,
+    pub vesting_type: VestingType,
+    pub beneficiary: AccountId,
+    #[serde(with = "u128_dec_format")]
+    pub total_vesting_amount: Balance,
+    #[serde(with = "u128_dec_format")]
+    pub claimed_token_amount: Balance,
+    #[serde(with = "u128_dec_format")]
+    pub released_amount: Balance,
+    #[serde(with = "u128_dec_format")]
+    pub claimable_amount: Balance,
+    // None if all tokens are released
+    pub next_unlock_time: Option<U64>,
+    #[serde(with = "u128_dec_format")]
+    pub next_unlock_amount: Balance,
+    // when all tokens are released
+    #[serde(with = "u64_dec_format")]
+    pub finish_time: SecondTimeStamp,
+    pub status: VestingStatus,
+}
+
+impl Vesting {
+    /// The next time after the time when tokens are released, which is the next checkpoint
+    /// of a cliff vesting, or the next second of a linear vesting as it releases every second.
+    pub fn get_next_unlock_time(&self, time: SecondTimeStamp) -> Option<SecondTimeStamp> {
+        match self {
+            Vesting::NaturalTimeLinearVesting(linear) => {
+                let next_time = (time + 1).max(linear.get_start_time());
+                if next_time <= linear.get_end_time() {
+                    Some(next_time)
+                } else {
+                    None
+                }
+            }
+            Vesting::TimeCliffVesting(cliff) => cliff
+                .time_cliff_list
+                .iter()
+                .map(|e| e.time)
+                .filter(|e| *e > time)
+                .min(),
+        }
+    }
+
+    pub fn get_view_at(&self, time: SecondTimeStamp) -> VestingView {
+        let token_info = self.get_vesting_token_info();
+        let released_amount = self.get_released_amount_at(time);
+        let next_unlock_time = self.get_next_unlock_time(time);
+        VestingView {
+            vesting_id: self.get_vesting_id(),
+            vesting_type: self.get_vesting_type(),
+            beneficiary: self.get_beneficiary(),
+            total_vesting_amount: token_info.total_vesting_amount,
+            claimed_token_amount: token_info.claimed_token_amount,
+            released_amount,
+            claimable_amount: self.get_claimable_amount_at(time),
+            next_unlock_time: next_unlock_time.map(U64),
+            next_unlock_amount: next_unlock_time
+                .map_or(0, |e| self.get_released_amount_at(e) - released_amount),
+            finish_time: self.get_end_time(),
+            status: if self.is_frozen() {
+                VestingStatus::Frozen
+            } else {
+                VestingStatus::Active
+            },
+        }
+    }
+
+    pub fn get_view(&self) -> VestingView {
+        self.get_view_at(get_block_second_time())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::vesting::cliff::{CliffVestingCheckpoint, TimeCliffVesting};
+    use crate::vesting::linear::NaturalTimeLinearVesting;
+    use crate::vesting::VestingTokenInfo;
+    use near_sdk::test_utils::test_env::bob;
+
+    #[test]
+    fn test_cliff_view() {
+        let vesting = Vesting::TimeCliffVesting(TimeCliffVesting {
+            id: U64(1),
+            beneficiary: bob(),
+            time_cliff_list: vec![
+                CliffVestingCheckpoint {
+                    time: 100,
+                    amount: 10,
+                },
+                CliffVestingCheckpoint {
+                    time: 300,
+                    amount: 20,
+                },
+            ],
+            vesting_token_info: VestingTokenInfo {
+                claimed_token_amount: 5,
+                total_vesting_amount: 30,
+            },
+            is_frozen: true,
+            create_time: 0,
+        });
+        let view = vesting.get_view_at(100);
+        assert_eq!(view.vesting_type, VestingType::Cliff);
+        assert_eq!(view.released_amount, 10);
+        assert_eq!(view.claimable_amount, 5);
+        assert_eq!(view.next_unlock_time, Some(U64(300)));
+        assert_eq!(view.next_unlock_amount, 20);
+        assert_eq!(view.finish_time, 300);
+        assert_eq!(view.status, VestingStatus::Frozen);
+        assert_eq!(vesting.get_view_at(300).next_unlock_time, None);
+    }
+
+    #[test]
+    fn test_linear_view() {
+        let vesting = Vesting::NaturalTimeLinearVesting(NaturalTimeLinearVesting {
+            id: U64(1),
+            beneficiary: bob(),
+            start_time: 1,
+            end_time: 10,
+            vesting_token_info: VestingTokenInfo {
+                claimed_token_amount: 0,
+                total_vesting_amount: 100,
+            },
+            is_frozen: false,
+            create_time: 0,
+        });
+        let view = vesting.get_view_at(0);
+        assert_eq!(view.vesting_type, VestingType::Linear);
+        assert_eq!(view.next_unlock_time, Some(U64(1)));
+        assert_eq!(view.next_unlock_amount, 10);
+        assert_eq!(view.finish_time, 10);
+        assert_eq!(view.status, VestingStatus::Active);
+        assert_eq!(vesting.get_view_at(10).next_unlock_time, None);
+    }
+}
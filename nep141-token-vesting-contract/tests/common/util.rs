@@ -63,6 +63,12 @@ pub async fn setup_vesting<'s>(
         account: oct,
     };
 
+    // a pool is required by default, most tests create vestings without one
+    vesting_contract
+        .set_pool_required(&owner, false)
+        .await
+        .unwrap();
+
     (vesting_contract, oct_contract, root, owner, beneficiary)
 }
 
@@ -1,9 +1,29 @@
-use near_sdk::json_types::{U128, U64};
+use near_contract_standards::storage_management::StorageBalance;
+use near_sdk::json_types::{Base58CryptoHash, U128, U64};
 use near_sdk::serde_json::json;
 use near_sdk::ONE_NEAR;
+use nep141_token_vesting_contract::claim_history::ClaimRecord;
+use nep141_token_vesting_contract::claim_policy::ClaimPolicy;
+use nep141_token_vesting_contract::distribution::{
+    AutoDistribution, DistributeResult, DistributionFloors, DistributionTip,
+};
 use nep141_token_vesting_contract::interfaces::OwnerAction;
+use nep141_token_vesting_contract::pool::VestingPoolView;
+use nep141_token_vesting_contract::roles::Role;
+use nep141_token_vesting_contract::stats::VestingStats;
+use nep141_token_vesting_contract::storage::VestingStorage;
+use nep141_token_vesting_contract::template::{VestingTemplate, VestingTemplateView};
+use nep141_token_vesting_contract::timelock::{ScheduledAction, SensitiveAction};
 use nep141_token_vesting_contract::types::VestingId;
+use nep141_token_vesting_contract::types::{
+    AirdropId, PoolId, ProposalId, ScheduledActionId, TemplateId,
+};
+use nep141_token_vesting_contract::vesting::archive::{ArchivedVesting, VestingStatus};
 use nep141_token_vesting_contract::vesting::cliff::CliffVestingCheckpoint;
+use nep141_token_vesting_contract::vesting::filter::VestingFilter;
+use nep141_token_vesting_contract::vesting::metadata::VestingMetadata;
+use nep141_token_vesting_contract::vesting::view::VestingView;
+use nep141_token_vesting_contract::vesting::{VestingCreateParam, VestingSelector};
 use workspaces::network::Sandbox;
 use workspaces::result::CallExecutionDetails;
 use workspaces::AccountId;
@@ -80,6 +100,93 @@ impl<'s> VestingContract<'s> {
             .unwrap()
     }
 
+    pub async fn get_filtered_vesting(
+        &self,
+        from_index: u32,
+        limit: u32,
+        beneficiary: Option<AccountId>,
+        filter: VestingFilter,
+    ) -> Vec<nep141_token_vesting_contract::vesting::Vesting> {
+        self.worker
+            .view(
+                &self.contract_id,
+                "get_vesting",
+                json!({
+                    "from_index": from_index,
+                    "limit": limit,
+                    "beneficiary": beneficiary,
+                    "filter": filter,
+                })
+                .to_string()
+                .into_bytes(),
+            )
+            .await
+            .unwrap()
+            .json()
+            .unwrap()
+    }
+
+    pub async fn get_vesting_view(&self, vesting_id: VestingId) -> Option<VestingView> {
+        self.worker
+            .view(
+                &self.contract_id,
+                "get_vesting_view",
+                json!({
+                    "vesting_id": vesting_id,
+                })
+                .to_string()
+                .into_bytes(),
+            )
+            .await
+            .unwrap()
+            .json()
+            .unwrap()
+    }
+
+    pub async fn get_vesting_views(
+        &self,
+        from_index: u32,
+        limit: u32,
+        beneficiary: Option<AccountId>,
+    ) -> Vec<VestingView> {
+        self.worker
+            .view(
+                &self.contract_id,
+                "get_vesting_views",
+                json!({
+                    "from_index": from_index,
+                    "limit": limit,
+                    "beneficiary": beneficiary,
+                })
+                .to_string()
+                .into_bytes(),
+            )
+            .await
+            .unwrap()
+            .json()
+            .unwrap()
+    }
+
+    pub async fn get_vestings_by_ids(
+        &self,
+        vesting_ids: Vec<VestingId>,
+    ) -> Vec<Option<nep141_token_vesting_contract::vesting::Vesting>> {
+        self.worker
+            .view(
+                &self.contract_id,
+                "get_vestings_by_ids",
+                json!({
+                    "vesting_ids": vesting_ids,
+                })
+                .to_string()
+                .into_bytes(),
+            )
+            .await
+            .unwrap()
+            .json()
+            .unwrap()
+    }
+
     pub async fn get_vesting_by_id(
         &self,
         id: VestingId,
@@ -137,132 +244,1086 @@ impl<'s> VestingContract<'s> {
             .unwrap()
     }
 
-    pub async fn create_linear_vesting(
+    pub async fn get_role_members(&self, role: Role) -> Vec<AccountId> {
+        self.worker
+            .view(
+                &self.contract_id,
+                "get_role_members",
+                json!({
+                    "role": role,
+                })
+                .to_string()
+                .into_bytes(),
+            )
+            .await
+            .unwrap()
+            .json()
+            .unwrap()
+    }
+
+    pub async fn get_scheduled_actions(&self, from_index: u32, limit: u32) -> Vec<ScheduledAction> {
+        self.worker
+            .view(
+                &self.contract_id,
+                "get_scheduled_actions",
+                json!({
+                    "from_index": from_index,
+                    "limit": limit,
+                })
+                .to_string()
+                .into_bytes(),
+            )
+            .await
+            .unwrap()
+            .json()
+            .unwrap()
+    }
+
+    pub async fn is_paused(&self) -> bool {
+        self.worker
+            .view(
+                &self.contract_id,
+                "is_paused",
+                json!({}).to_string().into_bytes(),
+            )
+            .await
+            .unwrap()
+            .json()
+            .unwrap()
+    }
+
+    pub async fn get_vesting_pool(&self, pool_id: PoolId) -> Option<VestingPoolView> {
+        self.worker
+            .view(
+                &self.contract_id,
+                "get_vesting_pool",
+                json!({
+                    "pool_id": pool_id,
+                })
+                .to_string()
+                .into_bytes(),
+            )
+            .await
+            .unwrap()
+            .json()
+            .unwrap()
+    }
+
+    pub async fn get_vesting_metadata(&self, vesting_id: VestingId) -> Option<VestingMetadata> {
+        self.worker
+            .view(
+                &self.contract_id,
+                "get_vesting_metadata",
+                json!({
+                    "vesting_id": vesting_id,
+                })
+                .to_string()
+                .into_bytes(),
+            )
+            .await
+            .unwrap()
+            .json()
+            .unwrap()
+    }
+
+    pub async fn get_vestings_by_tag(
+        &self,
+        tag: String,
+        from_index: u32,
+        limit: u32,
+    ) -> Vec<nep141_token_vesting_contract::vesting::Vesting> {
+        self.worker
+            .view(
+                &self.contract_id,
+                "get_vestings_by_tag",
+                json!({
+                    "tag": tag,
+                    "from_index": from_index,
+                    "limit": limit,
+                })
+                .to_string()
+                .into_bytes(),
+            )
+            .await
+            .unwrap()
+            .json()
+            .unwrap()
+    }
+
+    pub async fn set_vesting_metadata(
         &self,
         signer: &workspaces::Account,
-        beneficiary: AccountId,
-        start_time: U64,
-        end_time: U64,
-        total_vesting_amount: U128,
+        vesting_id: VestingId,
+        metadata: Option<VestingMetadata>,
     ) -> anyhow::Result<CallExecutionDetails> {
         signer
-            .call(self.worker, &self.contract_id, "create_linear_vesting")
+            .call(self.worker, &self.contract_id, "set_vesting_metadata")
             .deposit(ONE_NEAR)
             .args_json(json!({
-                "beneficiary": beneficiary,
-                "start_time": start_time,
-                "end_time": end_time ,
-                "total_vesting_amount": total_vesting_amount
+                "vesting_id": vesting_id,
+                "metadata": metadata,
             }))?
             .transact()
             .await
     }
 
-    pub async fn create_cliff_vesting(
+    pub async fn create_vesting_pool(
         &self,
         signer: &workspaces::Account,
-        beneficiary: AccountId,
-        time_cliff_list: Vec<CliffVestingCheckpoint>,
+        pool_id: PoolId,
+        cap: U128,
     ) -> anyhow::Result<CallExecutionDetails> {
         signer
-            .call(self.worker, &self.contract_id, "create_cliff_vesting")
+            .call(self.worker, &self.contract_id, "create_vesting_pool")
             .deposit(ONE_NEAR)
             .args_json(json!({
-                "beneficiary": beneficiary,
-                "time_cliff_list": time_cliff_list,
+                "pool_id": pool_id,
+                "cap": cap,
             }))?
             .transact()
             .await
     }
 
-    pub async fn freeze_vesting(
+    pub async fn set_pool_required(
         &self,
         signer: &workspaces::Account,
-        vesting_id: VestingId,
+        required: bool,
     ) -> anyhow::Result<CallExecutionDetails> {
         signer
-            .call(self.worker, &self.contract_id, "freeze_vesting")
+            .call(self.worker, &self.contract_id, "set_pool_required")
             .args_json(json!({
-                "vesting_id": vesting_id,
+                "required": required,
             }))?
             .transact()
             .await
     }
 
-    pub async fn unfreeze_vesting(
+    pub async fn create_pool_linear_vesting(
         &self,
         signer: &workspaces::Account,
-        vesting_id: VestingId,
+        beneficiary: AccountId,
+        start_time: U64,
+        end_time: U64,
+        total_vesting_amount: U128,
+        pool_id: PoolId,
     ) -> anyhow::Result<CallExecutionDetails> {
         signer
-            .call(self.worker, &self.contract_id, "unfreeze_vesting")
+            .call(self.worker, &self.contract_id, "create_linear_vesting")
+            .deposit(ONE_NEAR)
             .args_json(json!({
-                "vesting_id": vesting_id,
+                "beneficiary": beneficiary,
+                "start_time": start_time,
+                "end_time": end_time,
+                "total_vesting_amount": total_vesting_amount,
+                "pool_id": pool_id,
             }))?
             .transact()
             .await
     }
 
-    pub async fn terminate_vesting(
+    pub async fn get_vesting_templates(
+        &self,
+        from_index: u32,
+        limit: u32,
+    ) -> Vec<VestingTemplateView> {
+        self.worker
+            .view(
+                &self.contract_id,
+                "get_vesting_templates",
+                json!({
+                    "from_index": from_index,
+                    "limit": limit,
+                })
+                .to_string()
+                .into_bytes(),
+            )
+            .await
+            .unwrap()
+            .json()
+            .unwrap()
+    }
+
+    pub async fn set_vesting_template(
         &self,
         signer: &workspaces::Account,
-        vesting_id: VestingId,
+        template_id: TemplateId,
+        template: VestingTemplate,
     ) -> anyhow::Result<CallExecutionDetails> {
         signer
-            .call(self.worker, &self.contract_id, "terminate_vesting")
+            .call(self.worker, &self.contract_id, "set_vesting_template")
+            .deposit(ONE_NEAR)
             .args_json(json!({
-                "vesting_id": vesting_id,
+                "template_id": template_id,
+                "template": template,
             }))?
             .transact()
             .await
     }
 
-    pub async fn change_beneficiary(
+    pub async fn create_vesting_from_template(
         &self,
         signer: &workspaces::Account,
-        vesting_id: VestingId,
-        new_beneficiary: AccountId,
+        template_id: TemplateId,
+        beneficiary: AccountId,
+        start_time: U64,
+        amount: U128,
     ) -> anyhow::Result<CallExecutionDetails> {
         signer
-            .call(self.worker, &self.contract_id, "change_beneficiary")
+            .call(
+                self.worker,
+                &self.contract_id,
+                "create_vesting_from_template",
+            )
             .deposit(ONE_NEAR)
             .args_json(json!({
-                "vesting_id": vesting_id,
-                "new_beneficiary": new_beneficiary
+                "template_id": template_id,
+                "beneficiary": beneficiary,
+                "start_time": start_time,
+                "amount": amount,
             }))?
             .transact()
             .await
     }
 
-    pub async fn claim(
+    pub async fn create_linear_vesting(
         &self,
         signer: &workspaces::Account,
-        vesting_id: VestingId,
-        amount: Option<U128>,
+        beneficiary: AccountId,
+        start_time: U64,
+        end_time: U64,
+        total_vesting_amount: U128,
     ) -> anyhow::Result<CallExecutionDetails> {
         signer
-            .call(self.worker, &self.contract_id, "claim")
-            .max_gas()
+            .call(self.worker, &self.contract_id, "create_linear_vesting")
+            .deposit(ONE_NEAR)
             .args_json(json!({
-                "vesting_id": vesting_id,
-                "amount": amount
+                "beneficiary": beneficiary,
+                "start_time": start_time,
+                "end_time": end_time ,
+                "total_vesting_amount": total_vesting_amount
             }))?
             .transact()
             .await
     }
 
-    pub async fn claim_all(
+    pub async fn create_cliff_vesting(
         &self,
         signer: &workspaces::Account,
-        beneficiary: Option<AccountId>,
+        beneficiary: AccountId,
+        time_cliff_list: Vec<CliffVestingCheckpoint>,
+    ) -> anyhow::Result<CallExecutionDetails> {
+        signer
+            .call(self.worker, &self.contract_id, "create_cliff_vesting")
+            .deposit(ONE_NEAR)
+            .args_json(json!({
+                "beneficiary": beneficiary,
+                "time_cliff_list": time_cliff_list,
+            }))?
+            .transact()
+            .await
+    }
+
+    pub async fn create_vestings(
+        &self,
+        signer: &workspaces::Account,
+        params: Vec<VestingCreateParam>,
+    ) -> anyhow::Result<CallExecutionDetails> {
+        signer
+            .call(self.worker, &self.contract_id, "create_vestings")
+            .deposit(ONE_NEAR)
+            .max_gas()
+            .args_json(json!({
+                "params": params,
+            }))?
+            .transact()
+            .await
+    }
+
+    pub async fn freeze_vesting(
+        &self,
+        signer: &workspaces::Account,
+        vesting_id: VestingId,
+    ) -> anyhow::Result<CallExecutionDetails> {
+        signer
+            .call(self.worker, &self.contract_id, "freeze_vesting")
+            .args_json(json!({
+                "vesting_id": vesting_id,
+            }))?
+            .transact()
+            .await
+    }
+
+    pub async fn unfreeze_vesting(
+        &self,
+        signer: &workspaces::Account,
+        vesting_id: VestingId,
+    ) -> anyhow::Result<CallExecutionDetails> {
+        signer
+            .call(self.worker, &self.contract_id, "unfreeze_vesting")
+            .args_json(json!({
+                "vesting_id": vesting_id,
+            }))?
+            .transact()
+            .await
+    }
+
+    pub async fn terminate_vesting(
+        &self,
+        signer: &workspaces::Account,
+        vesting_id: VestingId,
+    ) -> anyhow::Result<CallExecutionDetails> {
+        signer
+            .call(self.worker, &self.contract_id, "terminate_vesting")
+            .args_json(json!({
+                "vesting_id": vesting_id,
+            }))?
+            .transact()
+            .await
+    }
+
+    pub async fn bulk_freeze_vesting(
+        &self,
+        signer: &workspaces::Account,
+        selector: VestingSelector,
+        from_index: u32,
+        limit: u32,
+    ) -> anyhow::Result<CallExecutionDetails> {
+        signer
+            .call(self.worker, &self.contract_id, "bulk_freeze_vesting")
+            .max_gas()
+            .args_json(json!({
+                "selector": selector,
+                "from_index": from_index,
+                "limit": limit,
+            }))?
+            .transact()
+            .await
+    }
+
+    pub async fn bulk_terminate_vesting(
+        &self,
+        signer: &workspaces::Account,
+        selector: VestingSelector,
+        from_index: u32,
+        limit: u32,
+    ) -> anyhow::Result<CallExecutionDetails> {
+        signer
+            .call(self.worker, &self.contract_id, "bulk_terminate_vesting")
+            .max_gas()
+            .args_json(json!({
+                "selector": selector,
+                "from_index": from_index,
+                "limit": limit,
+            }))?
+            .transact()
+            .await
+    }
+
+    pub async fn grant_role(
+        &self,
+        signer: &workspaces::Account,
+        role: Role,
+        account_id: AccountId,
+    ) -> anyhow::Result<CallExecutionDetails> {
+        signer
+            .call(self.worker, &self.contract_id, "grant_role")
+            .deposit(ONE_NEAR)
+            .args_json(json!({
+                "role": role,
+                "account_id": account_id,
+            }))?
+            .transact()
+            .await
+    }
+
+    pub async fn revoke_role(
+        &self,
+        signer: &workspaces::Account,
+        role: Role,
+        account_id: AccountId,
+    ) -> anyhow::Result<CallExecutionDetails> {
+        signer
+            .call(self.worker, &self.contract_id, "revoke_role")
+            .args_json(json!({
+                "role": role,
+                "account_id": account_id,
+            }))?
+            .transact()
+            .await
+    }
+
+    pub async fn set_timelock_delay(
+        &self,
+        signer: &workspaces::Account,
+        delay: U64,
+    ) -> anyhow::Result<CallExecutionDetails> {
+        signer
+            .call(self.worker, &self.contract_id, "set_timelock_delay")
+            .args_json(json!({
+                "delay": delay,
+            }))?
+            .transact()
+            .await
+    }
+
+    pub async fn schedule_action(
+        &self,
+        signer: &workspaces::Account,
+        action: SensitiveAction,
+    ) -> anyhow::Result<CallExecutionDetails> {
+        signer
+            .call(self.worker, &self.contract_id, "schedule_action")
+            .deposit(ONE_NEAR)
+            .args_json(json!({
+                "action": action,
+            }))?
+            .transact()
+            .await
+    }
+
+    pub async fn cancel_scheduled_action(
+        &self,
+        signer: &workspaces::Account,
+        action_id: ScheduledActionId,
+    ) -> anyhow::Result<CallExecutionDetails> {
+        signer
+            .call(self.worker, &self.contract_id, "cancel_scheduled_action")
+            .args_json(json!({
+                "action_id": action_id,
+            }))?
+            .transact()
+            .await
+    }
+
+    pub async fn execute_scheduled_action(
+        &self,
+        signer: &workspaces::Account,
+        action_id: ScheduledActionId,
+    ) -> anyhow::Result<CallExecutionDetails> {
+        signer
+            .call(self.worker, &self.contract_id, "execute_scheduled_action")
+            .max_gas()
+            .args_json(json!({
+                "action_id": action_id,
+            }))?
+            .transact()
+            .await
+    }
+
+    pub async fn set_approvers(
+        &self,
+        signer: &workspaces::Account,
+        approvers: Vec<AccountId>,
+        threshold: u32,
+    ) -> anyhow::Result<CallExecutionDetails> {
+        signer
+            .call(self.worker, &self.contract_id, "set_approvers")
+            .deposit(ONE_NEAR)
+            .args_json(json!({
+                "approvers": approvers,
+                "threshold": threshold,
+            }))?
+            .transact()
+            .await
+    }
+
+    pub async fn propose_action(
+        &self,
+        signer: &workspaces::Account,
+        action: SensitiveAction,
+    ) -> anyhow::Result<CallExecutionDetails> {
+        signer
+            .call(self.worker, &self.contract_id, "propose_action")
+            .deposit(ONE_NEAR)
+            .max_gas()
+            .args_json(json!({
+                "action": action,
+            }))?
+            .transact()
+            .await
+    }
+
+    pub async fn approve_proposal(
+        &self,
+        signer: &workspaces::Account,
+        proposal_id: ProposalId,
+    ) -> anyhow::Result<CallExecutionDetails> {
+        signer
+            .call(self.worker, &self.contract_id, "approve_proposal")
+            .deposit(ONE_NEAR)
+            .max_gas()
+            .args_json(json!({
+                "proposal_id": proposal_id,
+            }))?
+            .transact()
+            .await
+    }
+
+    pub async fn pause_contract(
+        &self,
+        signer: &workspaces::Account,
+    ) -> anyhow::Result<CallExecutionDetails> {
+        signer
+            .call(self.worker, &self.contract_id, "pause_contract")
+            .transact()
+            .await
+    }
+
+    pub async fn resume_contract(
+        &self,
+        signer: &workspaces::Account,
+    ) -> anyhow::Result<CallExecutionDetails> {
+        signer
+            .call(self.worker, &self.contract_id, "resume_contract")
+            .transact()
+            .await
+    }
+
+    pub async fn register_merkle_airdrop(
+        &self,
+        signer: &workspaces::Account,
+        root: Base58CryptoHash,
+        total_amount: U128,
+    ) -> anyhow::Result<CallExecutionDetails> {
+        signer
+            .call(self.worker, &self.contract_id, "register_merkle_airdrop")
+            .deposit(ONE_NEAR)
+            .args_json(json!({
+                "root": root,
+                "total_amount": total_amount,
+            }))?
+            .transact()
+            .await
+    }
+
+    pub async fn remove_merkle_airdrop(
+        &self,
+        signer: &workspaces::Account,
+        airdrop_id: AirdropId,
+    ) -> anyhow::Result<CallExecutionDetails> {
+        signer
+            .call(self.worker, &self.contract_id, "remove_merkle_airdrop")
+            .args_json(json!({
+                "airdrop_id": airdrop_id,
+            }))?
+            .transact()
+            .await
+    }
+
+    pub async fn claim_airdrop_vesting(
+        &self,
+        signer: &workspaces::Account,
+        airdrop_id: AirdropId,
+        param: VestingCreateParam,
+        proof: Vec<Base58CryptoHash>,
+    ) -> anyhow::Result<CallExecutionDetails> {
+        signer
+            .call(self.worker, &self.contract_id, "claim_airdrop_vesting")
+            .deposit(ONE_NEAR)
+            .max_gas()
+            .args_json(json!({
+                "airdrop_id": airdrop_id,
+                "param": param,
+                "proof": proof,
+            }))?
+            .transact()
+            .await
+    }
+
+    pub async fn change_beneficiary(
+        &self,
+        signer: &workspaces::Account,
+        vesting_id: VestingId,
+        new_beneficiary: AccountId,
+    ) -> anyhow::Result<CallExecutionDetails> {
+        signer
+            .call(self.worker, &self.contract_id, "change_beneficiary")
+            .deposit(ONE_NEAR)
+            .args_json(json!({
+                "vesting_id": vesting_id,
+                "new_beneficiary": new_beneficiary
+            }))?
+            .transact()
+            .await
+    }
+
+    pub async fn claim(
+        &self,
+        signer: &workspaces::Account,
+        vesting_id: VestingId,
+        amount: Option<U128>,
+    ) -> anyhow::Result<CallExecutionDetails> {
+        signer
+            .call(self.worker, &self.contract_id, "claim")
+            .max_gas()
+            .args_json(json!({
+                "vesting_id": vesting_id,
+                "amount": amount
+            }))?
+            .transact()
+            .await
+    }
+
+    pub async fn claim_to(
+        &self,
+        signer: &workspaces::Account,
+        vesting_id: VestingId,
+        receiver_id: AccountId,
+    ) -> anyhow::Result<CallExecutionDetails> {
+        signer
+            .call(self.worker, &self.contract_id, "claim")
+            .max_gas()
+            .args_json(json!({
+                "vesting_id": vesting_id,
+                "receiver_id": receiver_id,
+            }))?
+            .transact()
+            .await
+    }
+
+    pub async fn claim_all(
+        &self,
+        signer: &workspaces::Account,
+        beneficiary: Option<AccountId>,
+    ) -> anyhow::Result<CallExecutionDetails> {
+        signer
+            .call(self.worker, &self.contract_id, "claim_all")
+            .max_gas()
+            .args_json(json!({
+                "beneficiary": beneficiary,
+            }))?
+            .transact()
+            .await
+    }
+
+    pub async fn claim_all_paginated(
+        &self,
+        signer: &workspaces::Account,
+        beneficiary: Option<AccountId>,
+        from_index: u32,
+        limit: u32,
     ) -> anyhow::Result<CallExecutionDetails> {
         signer
             .call(self.worker, &self.contract_id, "claim_all")
             .max_gas()
             .args_json(json!({
                 "beneficiary": beneficiary,
+                "from_index": from_index,
+                "limit": limit,
+            }))?
+            .transact()
+            .await
+    }
+
+    pub async fn claim_many(
+        &self,
+        signer: &workspaces::Account,
+        vesting_ids: Vec<VestingId>,
+    ) -> anyhow::Result<CallExecutionDetails> {
+        signer
+            .call(self.worker, &self.contract_id, "claim_many")
+            .max_gas()
+            .args_json(json!({
+                "vesting_ids": vesting_ids,
+            }))?
+            .transact()
+            .await
+    }
+
+    pub async fn get_pending_balance(&self, account_id: AccountId) -> U128 {
+        self.worker
+            .view(
+                &self.contract_id,
+                "get_pending_balance",
+                json!({
+                    "account_id": account_id,
+                })
+                .to_string()
+                .into_bytes(),
+            )
+            .await
+            .unwrap()
+            .json()
+            .unwrap()
+    }
+
+    pub async fn claim_and_call(
+        &self,
+        signer: &workspaces::Account,
+        vesting_id: VestingId,
+        receiver_id: AccountId,
+        msg: String,
+    ) -> anyhow::Result<CallExecutionDetails> {
+        signer
+            .call(self.worker, &self.contract_id, "claim_and_call")
+            .max_gas()
+            .args_json(json!({
+                "vesting_id": vesting_id,
+                "receiver_id": receiver_id,
+                "msg": msg,
+            }))?
+            .transact()
+            .await
+    }
+
+    pub async fn withdraw_pending_balance(
+        &self,
+        signer: &workspaces::Account,
+    ) -> anyhow::Result<CallExecutionDetails> {
+        signer
+            .call(self.worker, &self.contract_id, "withdraw_pending_balance")
+            .max_gas()
+            .transact()
+            .await
+    }
+
+    pub async fn set_claim_policy(
+        &self,
+        signer: &workspaces::Account,
+        vesting_id: VestingId,
+        policy: ClaimPolicy,
+    ) -> anyhow::Result<CallExecutionDetails> {
+        signer
+            .call(self.worker, &self.contract_id, "set_claim_policy")
+            .deposit(ONE_NEAR)
+            .args_json(json!({
+                "vesting_id": vesting_id,
+                "policy": policy,
+            }))?
+            .transact()
+            .await
+    }
+
+    pub async fn add_claim_delegate(
+        &self,
+        signer: &workspaces::Account,
+        vesting_id: VestingId,
+        account_id: AccountId,
+    ) -> anyhow::Result<CallExecutionDetails> {
+        signer
+            .call(self.worker, &self.contract_id, "add_claim_delegate")
+            .deposit(ONE_NEAR)
+            .args_json(json!({
+                "vesting_id": vesting_id,
+                "account_id": account_id,
+            }))?
+            .transact()
+            .await
+    }
+
+    pub async fn remove_claim_delegate(
+        &self,
+        signer: &workspaces::Account,
+        vesting_id: VestingId,
+        account_id: AccountId,
+    ) -> anyhow::Result<CallExecutionDetails> {
+        signer
+            .call(self.worker, &self.contract_id, "remove_claim_delegate")
+            .args_json(json!({
+                "vesting_id": vesting_id,
+                "account_id": account_id,
+            }))?
+            .transact()
+            .await
+    }
+
+    pub async fn get_claim_policy(&self, vesting_id: VestingId) -> ClaimPolicy {
+        self.worker
+            .view(
+                &self.contract_id,
+                "get_claim_policy",
+                json!({
+                    "vesting_id": vesting_id,
+                })
+                .to_string()
+                .into_bytes(),
+            )
+            .await
+            .unwrap()
+            .json()
+            .unwrap()
+    }
+
+    pub async fn enable_auto_distribution(
+        &self,
+        signer: &workspaces::Account,
+        vesting_id: VestingId,
+        min_interval: U64,
+        min_amount: U128,
+    ) -> anyhow::Result<CallExecutionDetails> {
+        signer
+            .call(self.worker, &self.contract_id, "enable_auto_distribution")
+            .deposit(ONE_NEAR)
+            .args_json(json!({
+                "vesting_id": vesting_id,
+                "min_interval": min_interval,
+                "min_amount": min_amount,
+            }))?
+            .transact()
+            .await
+    }
+
+    pub async fn set_distribution_tip(
+        &self,
+        signer: &workspaces::Account,
+        tip: U128,
+    ) -> anyhow::Result<CallExecutionDetails> {
+        signer
+            .call(self.worker, &self.contract_id, "set_distribution_tip")
+            .args_json(json!({
+                "tip": tip,
+            }))?
+            .transact()
+            .await
+    }
+
+    pub async fn set_distribution_floors(
+        &self,
+        signer: &workspaces::Account,
+        min_interval: U64,
+        min_amount: U128,
+    ) -> anyhow::Result<CallExecutionDetails> {
+        signer
+            .call(self.worker, &self.contract_id, "set_distribution_floors")
+            .args_json(json!({
+                "min_interval": min_interval,
+                "min_amount": min_amount,
+            }))?
+            .transact()
+            .await
+    }
+
+    pub async fn get_distribution_floors(&self) -> DistributionFloors {
+        self.worker
+            .view(
+                &self.contract_id,
+                "get_distribution_floors",
+                json!({}).to_string().into_bytes(),
+            )
+            .await
+            .unwrap()
+            .json()
+            .unwrap()
+    }
+
+    pub async fn get_distribution_tip(&self) -> DistributionTip {
+        self.worker
+            .view(
+                &self.contract_id,
+                "get_distribution_tip",
+                json!({}).to_string().into_bytes(),
+            )
+            .await
+            .unwrap()
+            .json()
+            .unwrap()
+    }
+
+    pub async fn fund_distribution_tips(
+        &self,
+        signer: &workspaces::Account,
+        amount: u128,
+    ) -> anyhow::Result<CallExecutionDetails> {
+        signer
+            .call(self.worker, &self.contract_id, "fund_distribution_tips")
+            .deposit(amount)
+            .transact()
+            .await
+    }
+
+    pub async fn distribute(
+        &self,
+        signer: &workspaces::Account,
+        from_index: Option<u32>,
+        limit: Option<u32>,
+    ) -> anyhow::Result<DistributeResult> {
+        signer
+            .call(self.worker, &self.contract_id, "distribute")
+            .max_gas()
+            .args_json(json!({
+                "from_index": from_index,
+                "limit": limit,
+            }))?
+            .transact()
+            .await?
+            .json()
+    }
+
+    pub async fn get_auto_distribution(&self, vesting_id: VestingId) -> Option<AutoDistribution> {
+        self.worker
+            .view(
+                &self.contract_id,
+                "get_auto_distribution",
+                json!({
+                    "vesting_id": vesting_id,
+                })
+                .to_string()
+                .into_bytes(),
+            )
+            .await
+            .unwrap()
+            .json()
+            .unwrap()
+    }
+
+    pub async fn create_linear_vesting_with_deposit(
+        &self,
+        signer: &workspaces::Account,
+        beneficiary: AccountId,
+        start_time: U64,
+        end_time: U64,
+        total_vesting_amount: U128,
+        deposit: u128,
+    ) -> anyhow::Result<CallExecutionDetails> {
+        signer
+            .call(self.worker, &self.contract_id, "create_linear_vesting")
+            .deposit(deposit)
+            .args_json(json!({
+                "beneficiary": beneficiary,
+                "start_time": start_time,
+                "end_time": end_time,
+                "total_vesting_amount": total_vesting_amount
+            }))?
+            .transact()
+            .await
+    }
+
+    pub async fn storage_deposit(
+        &self,
+        signer: &workspaces::Account,
+        amount: u128,
+    ) -> anyhow::Result<CallExecutionDetails> {
+        signer
+            .call(self.worker, &self.contract_id, "storage_deposit")
+            .deposit(amount)
+            .args_json(json!({}))?
+            .transact()
+            .await
+    }
+
+    pub async fn storage_balance_of(&self, account_id: AccountId) -> Option<StorageBalance> {
+        self.worker
+            .view(
+                &self.contract_id,
+                "storage_balance_of",
+                json!({
+                    "account_id": account_id,
+                })
+                .to_string()
+                .into_bytes(),
+            )
+            .await
+            .unwrap()
+            .json()
+            .unwrap()
+    }
+
+    pub async fn get_vesting_storage(&self, vesting_id: VestingId) -> Option<VestingStorage> {
+        self.worker
+            .view(
+                &self.contract_id,
+                "get_vesting_storage",
+                json!({
+                    "vesting_id": vesting_id,
+                })
+                .to_string()
+                .into_bytes(),
+            )
+            .await
+            .unwrap()
+            .json()
+            .unwrap()
+    }
+
+    pub async fn set_archive_enabled(
+        &self,
+        signer: &workspaces::Account,
+        enabled: bool,
+    ) -> anyhow::Result<CallExecutionDetails> {
+        signer
+            .call(self.worker, &self.contract_id, "set_archive_enabled")
+            .args_json(json!({
+                "enabled": enabled,
             }))?
             .transact()
             .await
     }
+
+    pub async fn get_vesting_status(&self, vesting_id: VestingId) -> Option<VestingStatus> {
+        self.worker
+            .view(
+                &self.contract_id,
+                "get_vesting_status",
+                json!({
+                    "vesting_id": vesting_id,
+                })
+                .to_string()
+                .into_bytes(),
+            )
+            .await
+            .unwrap()
+            .json()
+            .unwrap()
+    }
+
+    pub async fn get_archived_vestings(
+        &self,
+        beneficiary: AccountId,
+        from_index: u32,
+        limit: u32,
+    ) -> Vec<ArchivedVesting> {
+        self.worker
+            .view(
+                &self.contract_id,
+                "get_archived_vestings",
+                json!({
+                    "beneficiary": beneficiary,
+                    "from_index": from_index,
+                    "limit": limit,
+                })
+                .to_string()
+                .into_bytes(),
+            )
+            .await
+            .unwrap()
+            .json()
+            .unwrap()
+    }
+
+    pub async fn get_claim_history(
+        &self,
+        vesting_id: VestingId,
+        from_index: u32,
+        limit: u32,
+    ) -> Vec<ClaimRecord> {
+        self.worker
+            .view(
+                &self.contract_id,
+                "get_claim_history",
+                json!({
+                    "vesting_id": vesting_id,
+                    "from_index": from_index,
+                    "limit": limit,
+                })
+                .to_string()
+                .into_bytes(),
+            )
+            .await
+            .unwrap()
+            .json()
+            .unwrap()
+    }
+
+    pub async fn get_stats(&self) -> VestingStats {
+        self.worker
+            .view(
+                &self.contract_id,
+                "get_stats",
+                json!({}).to_string().into_bytes(),
+            )
+            .await
+            .unwrap()
+            .json()
+            .unwrap()
+    }
 }
@@ -2,13 +2,18 @@ use crate::common::util::ResultAssert;
 use crate::common::util::{nano_to_seconds, register_account, setup_vesting};
 use crate::common::vesting::VestingContract;
 use near_sdk::json_types::{U128, U64};
-use near_sdk::Timestamp;
+use near_sdk::{Timestamp, ONE_NEAR};
+use nep141_token_vesting_contract::beneficiary::ClaimAllResult;
+use nep141_token_vesting_contract::claim_history::TransferStatus;
+use nep141_token_vesting_contract::claim_policy::ClaimPolicy;
+use nep141_token_vesting_contract::distribution::{DistributionFloors, DistributionTip};
+use nep141_token_vesting_contract::merkle::MerkleTree;
 use nep141_token_vesting_contract::vesting::cliff::{CliffVestingCheckpoint, TimeCliffVesting};
 use nep141_token_vesting_contract::vesting::linear::NaturalTimeLinearVesting;
 use nep141_token_vesting_contract::vesting::traits::{
     Beneficiary, Frozen, VestingAmount, VestingTokenInfoTrait,
 };
-use nep141_token_vesting_contract::vesting::{Vesting, VestingTokenInfo};
+use nep141_token_vesting_contract::vesting::{Vesting, VestingCreateParam, VestingTokenInfo};
 use nep141_token_vesting_contract::TokenVestingContract;
 use workspaces::AccountId;
 
@@ -165,3 +170,532 @@ async fn test_claim_call() -> anyhow::Result<()> {
 
     anyhow::Ok(())
 }
+
+#[tokio::test]
+async fn test_claim_airdrop_vesting() -> anyhow::Result<()> {
+    let worker = workspaces::sandbox().await.unwrap();
+    let (vesting_contract, oct_contract, root, owner, beneficiary) = setup_vesting(&worker).await;
+
+    let block = worker.view_latest_block().await.unwrap();
+    let now = nano_to_seconds(block.timestamp());
+
+    let leaves = vec![
+        VestingCreateParam::LinearVesting {
+            beneficiary: near_sdk::AccountId::new_unchecked(beneficiary.id().to_string()),
+            start_time: now - 1440 - 1440,
+            end_time: now - 1440,
+            total_vesting_amount: 10,
+        },
+        VestingCreateParam::LinearVesting {
+            beneficiary: near_sdk::AccountId::new_unchecked("someone.near".to_string()),
+            start_time: now - 1440 - 1440,
+            end_time: now - 1440,
+            total_vesting_amount: 20,
+        },
+    ];
+    let tree = MerkleTree::new(&leaves);
+    let proof = tree
+        .proof(0)
+        .into_iter()
+        .map(|e| e.into())
+        .collect::<Vec<_>>();
+
+    vesting_contract
+        .register_merkle_airdrop(&owner, tree.root().into(), U128(30))
+        .await?;
+    oct_contract
+        .mint(vesting_contract.contract_id.clone(), U128(30))
+        .await?;
+
+    let beneficiary_amount_before_claim =
+        oct_contract.ft_balance_of(beneficiary.id().clone()).await.0;
+    vesting_contract
+        .claim_airdrop_vesting(&beneficiary, U64(1), leaves[0].clone(), proof.clone())
+        .await?;
+    let beneficiary_amount_after_claim =
+        oct_contract.ft_balance_of(beneficiary.id().clone()).await.0;
+    assert_eq!(
+        beneficiary_amount_before_claim + 10,
+        beneficiary_amount_after_claim
+    );
+
+    let result = vesting_contract
+        .claim_airdrop_vesting(&beneficiary, U64(1), leaves[0].clone(), proof)
+        .await;
+    assert!(result
+        .unwrap_err()
+        .to_string()
+        .contains("The airdrop vesting has been claimed."));
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_claim_all_paginated() -> anyhow::Result<()> {
+    let worker = workspaces::sandbox().await.unwrap();
+    let (vesting_contract, oct_contract, root, owner, beneficiary) = setup_vesting(&worker).await;
+
+    let block = worker.view_latest_block().await.unwrap();
+    let now = nano_to_seconds(block.timestamp());
+
+    for _ in 0..3 {
+        vesting_contract
+            .create_linear_vesting(
+                &owner,
+                beneficiary.id().clone(),
+                U64(now - 1440 - 1440),
+                U64(now - 1440),
+                U128(10),
+            )
+            .await?;
+    }
+    oct_contract
+        .mint(vesting_contract.contract_id.clone(), U128(30))
+        .await?;
+
+    // the finished vestings are removed, so the next page starts at the same cursor
+    let result: ClaimAllResult = vesting_contract
+        .claim_all_paginated(&beneficiary, None, 0, 2)
+        .await?
+        .json()?;
+    assert_eq!(result.amount.0, 20);
+    assert_eq!(result.next_index, Some(0));
+
+    let result: ClaimAllResult = vesting_contract
+        .claim_all_paginated(&beneficiary, None, 0, 2)
+        .await?
+        .json()?;
+    assert_eq!(result.amount.0, 10);
+    assert_eq!(result.next_index, None);
+    assert_eq!(
+        oct_contract.ft_balance_of(beneficiary.id().clone()).await.0,
+        30
+    );
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_claim_many() -> anyhow::Result<()> {
+    let worker = workspaces::sandbox().await.unwrap();
+    let (vesting_contract, oct_contract, root, owner, beneficiary) = setup_vesting(&worker).await;
+
+    let block = worker.view_latest_block().await.unwrap();
+    let now = nano_to_seconds(block.timestamp());
+    let other_beneficiary = register_account(&worker, &root, "other_beneficiary").await;
+
+    for beneficiary_id in [beneficiary.id(), beneficiary.id(), other_beneficiary.id()] {
+        vesting_contract
+            .create_linear_vesting(
+                &owner,
+                beneficiary_id.clone(),
+                U64(now - 1440 - 1440),
+                U64(now - 1440),
+                U128(10),
+            )
+            .await?;
+    }
+    oct_contract
+        .mint(vesting_contract.contract_id.clone(), U128(30))
+        .await?;
+
+    let result = vesting_contract
+        .claim_many(&beneficiary, vec![U64(1), U64(3)])
+        .await;
+    assert!(result
+        .unwrap_err()
+        .to_string()
+        .contains("All vestings should belong to the same beneficiary."));
+
+    vesting_contract
+        .claim_many(&beneficiary, vec![U64(1), U64(2)])
+        .await?;
+    assert_eq!(
+        oct_contract.ft_balance_of(beneficiary.id().clone()).await.0,
+        20
+    );
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_claim_partial_amount() -> anyhow::Result<()> {
+    let worker = workspaces::sandbox().await.unwrap();
+    let (vesting_contract, oct_contract, root, owner, beneficiary) = setup_vesting(&worker).await;
+
+    let block = worker.view_latest_block().await.unwrap();
+    let now = nano_to_seconds(block.timestamp());
+
+    vesting_contract
+        .create_linear_vesting(
+            &owner,
+            beneficiary.id().clone(),
+            U64(now - 1440 - 1440),
+            U64(now - 1440),
+            U128(100),
+        )
+        .await?;
+    oct_contract
+        .mint(vesting_contract.contract_id.clone(), U128(100))
+        .await?;
+
+    let result = vesting_contract
+        .claim(&beneficiary, U64(1), Some(U128(101)))
+        .await;
+    assert!(result
+        .unwrap_err()
+        .to_string()
+        .contains("The amount to claim should be less or eq than the claimable amount 100."));
+
+    vesting_contract
+        .claim(&beneficiary, U64(1), Some(U128(30)))
+        .await?;
+    let vesting = vesting_contract.get_vesting_by_id(U64(1)).await;
+    assert_eq!(vesting.get_vesting_token_info().claimed_token_amount, 30);
+    assert_eq!(vesting_contract.get_claimable_amount(U64(1)).await.0, 70);
+    assert_eq!(
+        oct_contract.ft_balance_of(beneficiary.id().clone()).await.0,
+        30
+    );
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_claim_to_receiver() -> anyhow::Result<()> {
+    let worker = workspaces::sandbox().await.unwrap();
+    let (vesting_contract, oct_contract, root, owner, beneficiary) = setup_vesting(&worker).await;
+
+    let block = worker.view_latest_block().await.unwrap();
+    let now = nano_to_seconds(block.timestamp());
+
+    vesting_contract
+        .create_linear_vesting(
+            &owner,
+            beneficiary.id().clone(),
+            U64(now - 1440 - 1440),
+            U64(now + 1440),
+            U128(100),
+        )
+        .await?;
+    oct_contract
+        .mint(vesting_contract.contract_id.clone(), U128(100))
+        .await?;
+
+    // only the beneficiary can direct tokens to another receiver
+    let result = vesting_contract
+        .claim_to(&owner, U64(1), owner.id().clone())
+        .await;
+    assert!(result
+        .unwrap_err()
+        .to_string()
+        .contains("Only the beneficiary can claim tokens to another receiver."));
+
+    let receiver_amount_before_claim = oct_contract.ft_balance_of(owner.id().clone()).await.0;
+    vesting_contract
+        .claim_to(&beneficiary, U64(1), owner.id().clone())
+        .await?;
+    let vesting = vesting_contract.get_vesting_by_id(U64(1)).await;
+    let claimed_amount = vesting.get_vesting_token_info().claimed_token_amount;
+    assert!(claimed_amount > 0);
+    assert_eq!(
+        oct_contract.ft_balance_of(owner.id().clone()).await.0,
+        receiver_amount_before_claim + claimed_amount
+    );
+    assert_eq!(
+        oct_contract.ft_balance_of(beneficiary.id().clone()).await.0,
+        0
+    );
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_claim_and_call() -> anyhow::Result<()> {
+    let worker = workspaces::sandbox().await.unwrap();
+    let (vesting_contract, oct_contract, root, owner, beneficiary) = setup_vesting(&worker).await;
+
+    let block = worker.view_latest_block().await.unwrap();
+    let now = nano_to_seconds(block.timestamp());
+
+    vesting_contract
+        .create_linear_vesting(
+            &owner,
+            beneficiary.id().clone(),
+            U64(now - 1440 - 1440),
+            U64(now - 1440),
+            U128(100),
+        )
+        .await?;
+    oct_contract
+        .mint(vesting_contract.contract_id.clone(), U128(100))
+        .await?;
+
+    // the vesting contract itself refuses tokens for an unknown pool,
+    // so the whole amount is credited to the pending balance
+    vesting_contract
+        .claim_and_call(
+            &beneficiary,
+            U64(1),
+            vesting_contract.contract_id.clone(),
+            "no_such_pool".to_string(),
+        )
+        .await?;
+    assert_eq!(
+        vesting_contract
+            .get_pending_balance(beneficiary.id().clone())
+            .await
+            .0,
+        100
+    );
+
+    vesting_contract
+        .withdraw_pending_balance(&beneficiary)
+        .await?;
+    assert_eq!(
+        vesting_contract
+            .get_pending_balance(beneficiary.id().clone())
+            .await
+            .0,
+        0
+    );
+    assert_eq!(
+        oct_contract.ft_balance_of(beneficiary.id().clone()).await.0,
+        100
+    );
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_claim_policy() -> anyhow::Result<()> {
+    let worker = workspaces::sandbox().await.unwrap();
+    let (vesting_contract, oct_contract, root, owner, beneficiary) = setup_vesting(&worker).await;
+
+    let block = worker.view_latest_block().await.unwrap();
+    let now = nano_to_seconds(block.timestamp());
+
+    vesting_contract
+        .create_linear_vesting(
+            &owner,
+            beneficiary.id().clone(),
+            U64(now - 1440 - 1440),
+            U64(now + 1440),
+            U128(100),
+        )
+        .await?;
+    oct_contract
+        .mint(vesting_contract.contract_id.clone(), U128(100))
+        .await?;
+    assert_eq!(
+        vesting_contract.get_claim_policy(U64(1)).await,
+        ClaimPolicy::Anyone
+    );
+
+    // only the beneficiary can change the policy
+    assert!(vesting_contract
+        .set_claim_policy(&owner, U64(1), ClaimPolicy::BeneficiaryOnly)
+        .await
+        .unwrap_err()
+        .to_string()
+        .contains("Only the beneficiary can change the claim policy."));
+
+    vesting_contract
+        .set_claim_policy(&beneficiary, U64(1), ClaimPolicy::BeneficiaryAndDelegates)
+        .await?;
+    assert!(vesting_contract
+        .claim(&owner, U64(1), None)
+        .await
+        .unwrap_err()
+        .to_string()
+        .contains("doesn't allow"));
+
+    vesting_contract
+        .add_claim_delegate(&beneficiary, U64(1), owner.id().clone())
+        .await?;
+    vesting_contract.claim(&owner, U64(1), None).await?;
+    let vesting = vesting_contract.get_vesting_by_id(U64(1)).await;
+    assert!(vesting.get_vesting_token_info().claimed_token_amount > 0);
+
+    vesting_contract
+        .remove_claim_delegate(&beneficiary, U64(1), owner.id().clone())
+        .await?;
+    assert!(vesting_contract
+        .claim(&owner, U64(1), None)
+        .await
+        .unwrap_err()
+        .to_string()
+        .contains("doesn't allow"));
+    // the beneficiary can always claim
+    vesting_contract.claim(&beneficiary, U64(1), None).await?;
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_auto_distribution() -> anyhow::Result<()> {
+    let worker = workspaces::sandbox().await.unwrap();
+    let (vesting_contract, oct_contract, root, owner, beneficiary) = setup_vesting(&worker).await;
+    let keeper = register_account(&worker, &root, "keeper").await;
+
+    let block = worker.view_latest_block().await.unwrap();
+    let now = nano_to_seconds(block.timestamp());
+
+    vesting_contract
+        .create_linear_vesting(
+            &owner,
+            beneficiary.id().clone(),
+            U64(now - 1440 - 1440),
+            U64(now + 1440),
+            U128(100),
+        )
+        .await?;
+    vesting_contract
+        .create_linear_vesting(
+            &owner,
+            beneficiary.id().clone(),
+            U64(now - 1440 - 1440),
+            U64(now + 1440),
+            U128(100),
+        )
+        .await?;
+    oct_contract
+        .mint(vesting_contract.contract_id.clone(), U128(200))
+        .await?;
+
+    // the tips can't be set before the floors
+    assert!(vesting_contract
+        .set_distribution_tip(&owner, U128(ONE_NEAR / 100))
+        .await
+        .unwrap_err()
+        .to_string()
+        .contains("The distribution floors should be set greater than 0 before the tips."));
+    vesting_contract
+        .set_distribution_floors(&owner, U64(60), U128(1))
+        .await?;
+    assert_eq!(
+        vesting_contract.get_distribution_floors().await,
+        DistributionFloors {
+            min_interval: U64(60),
+            min_amount: U128(1),
+        }
+    );
+
+    // only the beneficiary can opt in
+    assert!(vesting_contract
+        .enable_auto_distribution(&keeper, U64(1), U64(3600), U128(1))
+        .await
+        .unwrap_err()
+        .to_string()
+        .contains("Only the beneficiary can change the auto distribution."));
+    vesting_contract
+        .enable_auto_distribution(&beneficiary, U64(1), U64(3600), U128(1))
+        .await?;
+    // the opt-in can't be below the floors
+    assert!(vesting_contract
+        .enable_auto_distribution(&beneficiary, U64(2), U64(0), U128(100))
+        .await
+        .unwrap_err()
+        .to_string()
+        .contains("The min interval should be greater or eq than 60."));
+    // the min amount is not reached
+    vesting_contract
+        .enable_auto_distribution(&beneficiary, U64(2), U64(60), U128(100))
+        .await?;
+
+    vesting_contract
+        .set_distribution_tip(&owner, U128(ONE_NEAR / 100))
+        .await?;
+    vesting_contract
+        .fund_distribution_tips(&owner, ONE_NEAR)
+        .await?;
+    assert_eq!(
+        vesting_contract.get_distribution_tip().await,
+        DistributionTip {
+            tip: U128(ONE_NEAR / 100),
+            balance: U128(ONE_NEAR),
+        }
+    );
+    assert!(vesting_contract
+        .set_distribution_floors(&owner, U64(0), U128(0))
+        .await
+        .unwrap_err()
+        .to_string()
+        .contains("The distribution floors should be greater than 0 while a tip is set."));
+
+    let result = vesting_contract.distribute(&keeper, None, None).await?;
+    assert_eq!(result.vesting_ids, vec![U64(1)]);
+    assert_eq!(result.tip, U128(ONE_NEAR / 100));
+    assert_eq!(result.next_index, None);
+    assert_eq!(
+        oct_contract.ft_balance_of(beneficiary.id().clone()).await,
+        result.amount
+    );
+    assert!(
+        vesting_contract
+            .get_auto_distribution(U64(1))
+            .await
+            .unwrap()
+            .last_distribute_time
+            > 0
+    );
+
+    // the min interval has not passed
+    let result = vesting_contract.distribute(&keeper, None, None).await?;
+    assert!(result.vesting_ids.is_empty());
+    assert_eq!(result.tip, U128(0));
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_claim_history() -> anyhow::Result<()> {
+    let worker = workspaces::sandbox().await.unwrap();
+    let (vesting_contract, oct_contract, root, owner, beneficiary) = setup_vesting(&worker).await;
+
+    let block = worker.view_latest_block().await.unwrap();
+    let now = nano_to_seconds(block.timestamp());
+
+    vesting_contract
+        .create_linear_vesting(
+            &owner,
+            beneficiary.id().clone(),
+            U64(now - 1440 - 1440),
+            U64(now + 1440),
+            U128(100),
+        )
+        .await?;
+    oct_contract
+        .mint(vesting_contract.contract_id.clone(), U128(100))
+        .await?;
+    assert!(vesting_contract
+        .get_claim_history(U64(1), 0, 10)
+        .await
+        .is_empty());
+
+    vesting_contract
+        .claim(&beneficiary, U64(1), Some(U128(10)))
+        .await?;
+    vesting_contract
+        .claim_to(&beneficiary, U64(1), owner.id().clone())
+        .await?;
+
+    let history = vesting_contract.get_claim_history(U64(1), 0, 10).await;
+    assert_eq!(history.len(), 2);
+    assert_eq!(history[0].amount, 10);
+    assert_eq!(history[0].receiver_id.as_str(), beneficiary.id().as_str());
+    assert_eq!(history[1].receiver_id.as_str(), owner.id().as_str());
+    assert!(history
+        .iter()
+        .all(|e| e.status == TransferStatus::Succeeded));
+    assert_eq!(
+        history.iter().map(|e| e.amount).sum::<u128>(),
+        vesting_contract
+            .get_vesting_by_id(U64(1))
+            .await
+            .get_vesting_token_info()
+            .claimed_token_amount
+    );
+
+    Ok(())
+}
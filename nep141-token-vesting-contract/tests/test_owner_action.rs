@@ -1,14 +1,23 @@
 use crate::common::util::ResultAssert;
 use crate::common::util::{nano_to_seconds, register_account, setup_vesting};
 use crate::common::vesting::VestingContract;
-use near_sdk::json_types::{U128, U64};
-use near_sdk::Timestamp;
+use near_sdk::json_types::{Base58CryptoHash, U128, U64};
+use near_sdk::serde_json::Value;
+use near_sdk::{Timestamp, ONE_NEAR};
+use nep141_token_vesting_contract::roles::Role;
+use nep141_token_vesting_contract::template::{TemplateCheckpoint, VestingTemplate};
+use nep141_token_vesting_contract::timelock::SensitiveAction;
+use nep141_token_vesting_contract::vesting::archive::VestingStatus;
 use nep141_token_vesting_contract::vesting::cliff::{CliffVestingCheckpoint, TimeCliffVesting};
+use nep141_token_vesting_contract::vesting::filter::{VestingFilter, VestingType};
 use nep141_token_vesting_contract::vesting::linear::NaturalTimeLinearVesting;
+use nep141_token_vesting_contract::vesting::metadata::VestingMetadata;
 use nep141_token_vesting_contract::vesting::traits::{
     Beneficiary, Frozen, VestingAmount, VestingTokenInfoTrait,
 };
-use nep141_token_vesting_contract::vesting::{Vesting, VestingTokenInfo};
+use nep141_token_vesting_contract::vesting::{
+    BulkActionResult, Vesting, VestingCreateParam, VestingSelector, VestingTokenInfo,
+};
 use nep141_token_vesting_contract::TokenVestingContract;
 use workspaces::AccountId;
 
@@ -275,3 +284,1205 @@ async fn test_terminate_vesting() -> anyhow::Result<()> {
 
     Ok(())
 }
+
+#[tokio::test]
+async fn test_role_based_access() -> anyhow::Result<()> {
+    let worker = workspaces::sandbox().await.unwrap();
+    let (vesting_contract, oct_contract, root, owner, beneficiary) = setup_vesting(&worker).await;
+
+    let block = worker.view_latest_block().await.unwrap();
+    let now = nano_to_seconds(block.timestamp());
+
+    let hr = register_account(&worker, &root, "hr").await;
+    let result = vesting_contract
+        .grant_role(&hr, Role::Creator, hr.id().clone())
+        .await;
+    assert!(result
+        .unwrap_err()
+        .to_string()
+        .contains("Owner must be predecessor"));
+
+    vesting_contract
+        .grant_role(&owner, Role::Creator, hr.id().clone())
+        .await?;
+    assert_eq!(
+        vesting_contract.get_role_members(Role::Creator).await,
+        vec![hr.id().clone()]
+    );
+
+    vesting_contract
+        .create_linear_vesting(
+            &hr,
+            beneficiary.id().clone(),
+            U64(now - 1440),
+            U64(now + 1440),
+            U128(100),
+        )
+        .await?;
+    let vesting = vesting_contract.get_vesting_by_id(U64(1)).await;
+    assert_eq!(vesting.get_vesting_id(), U64(1));
+
+    let result = vesting_contract.terminate_vesting(&hr, U64(1)).await;
+    assert!(result
+        .unwrap_err()
+        .to_string()
+        .contains("must have role Terminator"));
+
+    vesting_contract
+        .revoke_role(&owner, Role::Creator, hr.id().clone())
+        .await?;
+    let result = vesting_contract
+        .create_linear_vesting(
+            &hr,
+            beneficiary.id().clone(),
+            U64(now - 1440),
+            U64(now + 1440),
+            U128(100),
+        )
+        .await;
+    assert!(result
+        .unwrap_err()
+        .to_string()
+        .contains("must have role Creator"));
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_timelocked_terminate_vesting() -> anyhow::Result<()> {
+    let worker = workspaces::sandbox().await.unwrap();
+    let (vesting_contract, oct_contract, root, owner, beneficiary) = setup_vesting(&worker).await;
+
+    let block = worker.view_latest_block().await.unwrap();
+    let now = nano_to_seconds(block.timestamp());
+
+    vesting_contract
+        .create_linear_vesting(
+            &owner,
+            beneficiary.id().clone(),
+            U64(now - 1440),
+            U64(now + 1440),
+            U128(100),
+        )
+        .await?;
+
+    vesting_contract
+        .set_timelock_delay(&owner, U64(86400))
+        .await?;
+
+    let result = vesting_contract.terminate_vesting(&owner, U64(1)).await;
+    assert!(result
+        .unwrap_err()
+        .to_string()
+        .contains("This action should be scheduled through the timelock."));
+
+    vesting_contract
+        .schedule_action(
+            &owner,
+            SensitiveAction::TerminateVesting { vesting_id: U64(1) },
+        )
+        .await?;
+    let scheduled_actions = vesting_contract.get_scheduled_actions(0, 10).await;
+    assert_eq!(scheduled_actions.len(), 1);
+    let action_id = scheduled_actions[0].id;
+    assert_eq!(
+        scheduled_actions[0].executable_time,
+        scheduled_actions[0].schedule_time + 86400
+    );
+
+    let result = vesting_contract
+        .execute_scheduled_action(&owner, action_id)
+        .await;
+    assert!(result
+        .unwrap_err()
+        .to_string()
+        .contains("is not executable until"));
+
+    vesting_contract
+        .cancel_scheduled_action(&owner, action_id)
+        .await?;
+    assert!(vesting_contract
+        .get_scheduled_actions(0, 10)
+        .await
+        .is_empty());
+    assert_eq!(
+        vesting_contract
+            .get_vesting_by_id(U64(1))
+            .await
+            .get_vesting_id(),
+        U64(1)
+    );
+
+    // an invalid amendment is rejected when it is scheduled
+    let result = vesting_contract
+        .schedule_action(
+            &owner,
+            SensitiveAction::AmendVesting {
+                vesting_id: U64(1),
+                param: VestingCreateParam::LinearVesting {
+                    beneficiary: beneficiary.id().as_str().parse().unwrap(),
+                    start_time: now + 1440,
+                    end_time: now - 1440,
+                    total_vesting_amount: 100,
+                },
+            },
+        )
+        .await;
+    assert!(result
+        .unwrap_err()
+        .to_string()
+        .contains("End time should be less than start time"));
+
+    let result = vesting_contract
+        .set_approvers(&owner, vec![beneficiary.id().clone()], 1)
+        .await;
+    assert!(result
+        .unwrap_err()
+        .to_string()
+        .contains("This action should be scheduled through the timelock."));
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_amend_vesting_after_claim() -> anyhow::Result<()> {
+    let worker = workspaces::sandbox().await.unwrap();
+    let (vesting_contract, oct_contract, root, owner, beneficiary) = setup_vesting(&worker).await;
+
+    let block = worker.view_latest_block().await.unwrap();
+    let now = nano_to_seconds(block.timestamp());
+
+    vesting_contract
+        .create_linear_vesting(
+            &owner,
+            beneficiary.id().clone(),
+            U64(now - 1440),
+            U64(now + 1440),
+            U128(100),
+        )
+        .await?;
+    oct_contract
+        .mint(vesting_contract.contract_id.clone(), U128(100))
+        .await?;
+
+    // delay the schedule, which is valid before anything is claimed
+    let later_schedule = SensitiveAction::AmendVesting {
+        vesting_id: U64(1),
+        param: VestingCreateParam::LinearVesting {
+            beneficiary: beneficiary.id().as_str().parse().unwrap(),
+            start_time: now + 1440,
+            end_time: now + 2880,
+            total_vesting_amount: 100,
+        },
+    };
+    vesting_contract
+        .schedule_action(&owner, later_schedule.clone())
+        .await?;
+    let action_id = vesting_contract.get_scheduled_actions(0, 10).await[0].id;
+
+    // the beneficiary claims before the amendment is executed
+    vesting_contract.claim(&beneficiary, U64(1), None).await?;
+    let claimed_amount = vesting_contract
+        .get_vesting_by_id(U64(1))
+        .await
+        .get_vesting_token_info()
+        .claimed_token_amount;
+    assert!(claimed_amount > 0);
+
+    let result = vesting_contract
+        .execute_scheduled_action(&owner, action_id)
+        .await;
+    assert!(result.unwrap_err().to_string().contains(
+        "The released amount of the amended vesting should be greater or eq than the claimed amount."
+    ));
+    let result = vesting_contract
+        .schedule_action(&owner, later_schedule)
+        .await;
+    assert!(result.unwrap_err().to_string().contains(
+        "The released amount of the amended vesting should be greater or eq than the claimed amount."
+    ));
+
+    // the vesting keeps its schedule, so claims and views still work
+    vesting_contract.claim(&beneficiary, U64(1), None).await?;
+    let view = vesting_contract.get_vesting_view(U64(1)).await.unwrap();
+    assert!(view.released_amount >= view.claimed_token_amount);
+    vesting_contract.get_claimable_amount(U64(1)).await;
+    vesting_contract
+        .get_all_claimable_amount(Some(beneficiary.id().clone()))
+        .await;
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_multisig_terminate_vesting() -> anyhow::Result<()> {
+    let worker = workspaces::sandbox().await.unwrap();
+    let (vesting_contract, oct_contract, root, owner, beneficiary) = setup_vesting(&worker).await;
+
+    let block = worker.view_latest_block().await.unwrap();
+    let now = nano_to_seconds(block.timestamp());
+
+    vesting_contract
+        .create_linear_vesting(
+            &owner,
+            beneficiary.id().clone(),
+            U64(now - 1440),
+            U64(now + 1440),
+            U128(100),
+        )
+        .await?;
+
+    let approver1 = register_account(&worker, &root, "approver1").await;
+    let approver2 = register_account(&worker, &root, "approver2").await;
+    vesting_contract
+        .set_approvers(
+            &owner,
+            vec![approver1.id().clone(), approver2.id().clone()],
+            2,
+        )
+        .await?;
+
+    let result = vesting_contract.terminate_vesting(&owner, U64(1)).await;
+    assert!(result
+        .unwrap_err()
+        .to_string()
+        .contains("This action should be proposed to approvers."));
+
+    vesting_contract
+        .propose_action(
+            &approver1,
+            SensitiveAction::TerminateVesting { vesting_id: U64(1) },
+        )
+        .await?;
+    assert_eq!(vesting_contract.get_vesting(0, 10, None).await.len(), 1);
+
+    // the proposal id is assigned after the vesting id
+    vesting_contract
+        .approve_proposal(&approver2, U64(2))
+        .await?;
+    assert!(vesting_contract.get_vesting(0, 10, None).await.is_empty());
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_pause_contract() -> anyhow::Result<()> {
+    let worker = workspaces::sandbox().await.unwrap();
+    let (vesting_contract, oct_contract, root, owner, beneficiary) = setup_vesting(&worker).await;
+
+    let block = worker.view_latest_block().await.unwrap();
+    let now = nano_to_seconds(block.timestamp());
+
+    vesting_contract
+        .create_linear_vesting(
+            &owner,
+            beneficiary.id().clone(),
+            U64(now - 1440),
+            U64(now + 1440),
+            U128(100),
+        )
+        .await?;
+    oct_contract
+        .mint(vesting_contract.contract_id.clone(), U128(100))
+        .await?;
+
+    let guardian = register_account(&worker, &root, "guardian").await;
+    vesting_contract
+        .grant_role(&owner, Role::Guardian, guardian.id().clone())
+        .await?;
+    vesting_contract.pause_contract(&guardian).await?;
+    assert!(vesting_contract.is_paused().await);
+
+    let result = vesting_contract.claim(&beneficiary, U64(1), None).await;
+    assert!(result
+        .unwrap_err()
+        .to_string()
+        .contains("The contract is paused."));
+    let result = vesting_contract
+        .create_linear_vesting(
+            &owner,
+            beneficiary.id().clone(),
+            U64(now - 1440),
+            U64(now + 1440),
+            U128(100),
+        )
+        .await;
+    assert!(result
+        .unwrap_err()
+        .to_string()
+        .contains("The contract is paused."));
+
+    // a guardian can only pause the contract
+    let result = vesting_contract.resume_contract(&guardian).await;
+    assert!(result
+        .unwrap_err()
+        .to_string()
+        .contains("predecessor must have role Admin"));
+
+    vesting_contract.resume_contract(&owner).await?;
+    assert!(!vesting_contract.is_paused().await);
+    vesting_contract.claim(&beneficiary, U64(1), None).await?;
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_bulk_freeze_and_terminate_vesting() -> anyhow::Result<()> {
+    let worker = workspaces::sandbox().await.unwrap();
+    let (vesting_contract, oct_contract, root, owner, beneficiary) = setup_vesting(&worker).await;
+
+    let block = worker.view_latest_block().await.unwrap();
+    let now = nano_to_seconds(block.timestamp());
+
+    let other_beneficiary = register_account(&worker, &root, "other_beneficiary").await;
+    for account in [&beneficiary, &beneficiary, &other_beneficiary] {
+        vesting_contract
+            .create_linear_vesting(
+                &owner,
+                account.id().clone(),
+                U64(now - 1440),
+                U64(now + 1440),
+                U128(100),
+            )
+            .await?;
+    }
+
+    vesting_contract
+        .bulk_freeze_vesting(
+            &owner,
+            VestingSelector::Beneficiary(near_sdk::AccountId::new_unchecked(
+                beneficiary.id().to_string(),
+            )),
+            0,
+            10,
+        )
+        .await?;
+    assert!(vesting_contract.get_vesting_by_id(U64(1)).await.is_frozen());
+    assert!(vesting_contract.get_vesting_by_id(U64(2)).await.is_frozen());
+    assert!(!vesting_contract.get_vesting_by_id(U64(3)).await.is_frozen());
+
+    vesting_contract
+        .bulk_terminate_vesting(
+            &owner,
+            VestingSelector::VestingIds(vec![U64(1), U64(3)]),
+            0,
+            10,
+        )
+        .await?;
+    let vestings = vesting_contract.get_vesting(0, 10, None).await;
+    assert_eq!(vestings.len(), 1);
+    assert_eq!(vestings[0].get_vesting_id(), U64(2));
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_bulk_terminate_vesting_pages() -> anyhow::Result<()> {
+    let worker = workspaces::sandbox().await.unwrap();
+    let (vesting_contract, oct_contract, root, owner, beneficiary) = setup_vesting(&worker).await;
+
+    let block = worker.view_latest_block().await.unwrap();
+    let now = nano_to_seconds(block.timestamp());
+
+    for _ in 0..5 {
+        vesting_contract
+            .create_linear_vesting(
+                &owner,
+                beneficiary.id().clone(),
+                U64(now - 1440),
+                U64(now + 1440),
+                U128(100),
+            )
+            .await?;
+    }
+
+    // follow next_index, the terminated vestings don't make the next pages skip any vesting
+    let selector = VestingSelector::Beneficiary(near_sdk::AccountId::new_unchecked(
+        beneficiary.id().to_string(),
+    ));
+    let mut terminated_ids = vec![];
+    let mut from_index = Some(0);
+    while let Some(index) = from_index {
+        let result: BulkActionResult = vesting_contract
+            .bulk_terminate_vesting(&owner, selector.clone(), index, 2)
+            .await?
+            .json()?;
+        assert!(result.vesting_ids.len() <= 2);
+        terminated_ids.extend(result.vesting_ids);
+        from_index = result.next_index;
+    }
+    terminated_ids.sort_by_key(|e| e.0);
+    assert_eq!(terminated_ids, vec![U64(1), U64(2), U64(3), U64(4), U64(5)]);
+    assert!(vesting_contract.get_vesting(0, 10, None).await.is_empty());
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_create_vestings() -> anyhow::Result<()> {
+    let worker = workspaces::sandbox().await.unwrap();
+    let (vesting_contract, oct_contract, root, owner, beneficiary) = setup_vesting(&worker).await;
+
+    let block = worker.view_latest_block().await.unwrap();
+    let now = nano_to_seconds(block.timestamp());
+    let beneficiary_id = near_sdk::AccountId::new_unchecked(beneficiary.id().to_string());
+
+    vesting_contract
+        .create_vestings(
+            &owner,
+            vec![
+                VestingCreateParam::LinearVesting {
+                    beneficiary: beneficiary_id.clone(),
+                    start_time: now - 1440,
+                    end_time: now + 1440,
+                    total_vesting_amount: 100,
+                },
+                VestingCreateParam::CliffVesting {
+                    beneficiary: beneficiary_id.clone(),
+                    time_cliff_list: vec![CliffVestingCheckpoint {
+                        time: now + 1440,
+                        amount: 10,
+                    }],
+                },
+            ],
+        )
+        .await?;
+
+    let vestings = vesting_contract.get_vesting(0, 10, None).await;
+    assert_eq!(vestings.len(), 2);
+    assert!(matches!(
+        vesting_contract.get_vesting_by_id(U64(1)).await,
+        Vesting::NaturalTimeLinearVesting(_)
+    ));
+    assert!(matches!(
+        vesting_contract.get_vesting_by_id(U64(2)).await,
+        Vesting::TimeCliffVesting(_)
+    ));
+
+    // an invalid entry fails the whole batch
+    let result = vesting_contract
+        .create_vestings(
+            &owner,
+            vec![
+                VestingCreateParam::LinearVesting {
+                    beneficiary: beneficiary_id.clone(),
+                    start_time: now - 1440,
+                    end_time: now + 1440,
+                    total_vesting_amount: 100,
+                },
+                VestingCreateParam::LinearVesting {
+                    beneficiary: beneficiary_id,
+                    start_time: now + 1440,
+                    end_time: now - 1440,
+                    total_vesting_amount: 100,
+                },
+            ],
+        )
+        .await;
+    assert!(result.is_err());
+    assert_eq!(vesting_contract.get_vesting(0, 10, None).await.len(), 2);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_vesting_pool() -> anyhow::Result<()> {
+    let worker = workspaces::sandbox().await.unwrap();
+    let (vesting_contract, oct_contract, root, owner, beneficiary) = setup_vesting(&worker).await;
+
+    let block = worker.view_latest_block().await.unwrap();
+    let now = nano_to_seconds(block.timestamp());
+    let pool_id = "team".to_string();
+
+    vesting_contract
+        .create_vesting_pool(&owner, pool_id.clone(), U128(100))
+        .await?;
+    oct_contract.mint(owner.id().clone(), U128(80)).await?;
+    oct_contract
+        .ft_transfer_call(
+            &worker,
+            &owner,
+            vesting_contract.contract_id.clone(),
+            U128(80),
+            None,
+            pool_id.clone(),
+        )
+        .await?;
+
+    vesting_contract
+        .create_pool_linear_vesting(
+            &owner,
+            beneficiary.id().clone(),
+            U64(now - 1440),
+            U64(now + 1440),
+            U128(60),
+            pool_id.clone(),
+        )
+        .await?;
+    let pool = vesting_contract
+        .get_vesting_pool(pool_id.clone())
+        .await
+        .unwrap();
+    assert_eq!(pool.funded_amount, 80);
+    assert_eq!(pool.allocated_amount, 60);
+    assert_eq!(pool.remaining_amount, 40);
+    assert_eq!(pool.utilization, 6000);
+
+    // the cap can't be exceeded
+    let result = vesting_contract
+        .create_pool_linear_vesting(
+            &owner,
+            beneficiary.id().clone(),
+            U64(now - 1440),
+            U64(now + 1440),
+            U128(60),
+            pool_id.clone(),
+        )
+        .await;
+    assert!(result
+        .unwrap_err()
+        .to_string()
+        .contains("The cap of vesting pool team is exceeded."));
+
+    // the terminated vesting returns its amount to the pool
+    vesting_contract.terminate_vesting(&owner, U64(1)).await?;
+    let pool = vesting_contract.get_vesting_pool(pool_id).await.unwrap();
+    assert_eq!(pool.allocated_amount, 0);
+    assert_eq!(pool.utilization, 0);
+
+    // the pool can't be skipped once it is required again
+    vesting_contract.set_pool_required(&owner, true).await?;
+    let result = vesting_contract
+        .create_linear_vesting(
+            &owner,
+            beneficiary.id().clone(),
+            U64(now - 1440),
+            U64(now + 1440),
+            U128(60),
+        )
+        .await;
+    assert!(result
+        .unwrap_err()
+        .to_string()
+        .contains("A vesting pool is required."));
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_vesting_metadata() -> anyhow::Result<()> {
+    let worker = workspaces::sandbox().await.unwrap();
+    let (vesting_contract, oct_contract, root, owner, beneficiary) = setup_vesting(&worker).await;
+
+    let block = worker.view_latest_block().await.unwrap();
+    let now = nano_to_seconds(block.timestamp());
+
+    for _ in 0..2 {
+        vesting_contract
+            .create_linear_vesting(
+                &owner,
+                beneficiary.id().clone(),
+                U64(now - 1440),
+                U64(now + 1440),
+                U128(100),
+            )
+            .await?;
+    }
+    assert!(vesting_contract
+        .get_vesting_metadata(U64(1))
+        .await
+        .is_none());
+
+    let metadata = VestingMetadata {
+        title: Some("Seed grant".to_string()),
+        reference: Some("https://example.com/grants/1".to_string()),
+        tag: Some("team".to_string()),
+        ..Default::default()
+    };
+    let result = vesting_contract
+        .set_vesting_metadata(&beneficiary, U64(2), Some(metadata.clone()))
+        .await;
+    assert!(result
+        .unwrap_err()
+        .to_string()
+        .contains("Owner must be predecessor"));
+    vesting_contract
+        .set_vesting_metadata(&owner, U64(2), Some(metadata))
+        .await?;
+
+    assert_eq!(
+        vesting_contract
+            .get_vesting_metadata(U64(2))
+            .await
+            .unwrap()
+            .title,
+        Some("Seed grant".to_string())
+    );
+    let vestings = vesting_contract
+        .get_vestings_by_tag("team".to_string(), 0, 10)
+        .await;
+    assert_eq!(vestings.len(), 1);
+    assert_eq!(vestings[0].get_vesting_id(), U64(2));
+
+    // the tag index follows the metadata and the removal of the vesting
+    vesting_contract
+        .set_vesting_metadata(
+            &owner,
+            U64(1),
+            Some(VestingMetadata {
+                tag: Some("team".to_string()),
+                ..Default::default()
+            }),
+        )
+        .await?;
+    vesting_contract.terminate_vesting(&owner, U64(2)).await?;
+    let vestings = vesting_contract
+        .get_vestings_by_tag("team".to_string(), 0, 10)
+        .await;
+    assert_eq!(vestings.len(), 1);
+    assert_eq!(vestings[0].get_vesting_id(), U64(1));
+    vesting_contract
+        .set_vesting_metadata(&owner, U64(1), None)
+        .await?;
+    assert!(vesting_contract
+        .get_vestings_by_tag("team".to_string(), 0, 10)
+        .await
+        .is_empty());
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_vesting_storage_refund() -> anyhow::Result<()> {
+    let worker = workspaces::sandbox().await.unwrap();
+    let (vesting_contract, oct_contract, root, owner, beneficiary) = setup_vesting(&worker).await;
+
+    let block = worker.view_latest_block().await.unwrap();
+    let now = nano_to_seconds(block.timestamp());
+
+    // no deposit attached and no storage balance
+    assert!(vesting_contract
+        .create_linear_vesting_with_deposit(
+            &owner,
+            beneficiary.id().clone(),
+            U64(now - 1440),
+            U64(now + 1440),
+            U128(100),
+            0,
+        )
+        .await
+        .unwrap_err()
+        .to_string()
+        .contains("ERR_STORAGE_DEPOSIT"));
+
+    vesting_contract.storage_deposit(&owner, ONE_NEAR).await?;
+    let balance_before = vesting_contract
+        .storage_balance_of(owner.id().clone())
+        .await
+        .unwrap();
+    assert_eq!(balance_before.total, U128(ONE_NEAR));
+
+    // the storage is paid by the storage balance
+    vesting_contract
+        .create_linear_vesting_with_deposit(
+            &owner,
+            beneficiary.id().clone(),
+            U64(now - 1440),
+            U64(now + 1440),
+            U128(100),
+            0,
+        )
+        .await?;
+    let vesting_storage = vesting_contract.get_vesting_storage(U64(1)).await.unwrap();
+    assert_eq!(vesting_storage.payer.as_str(), owner.id().as_str());
+    assert!(vesting_storage.amount > 0);
+    let balance = vesting_contract
+        .storage_balance_of(owner.id().clone())
+        .await
+        .unwrap();
+    assert_eq!(balance.total, balance_before.total);
+    assert_eq!(
+        balance.available.0,
+        balance_before.available.0 - vesting_storage.amount
+    );
+
+    // the storage is refunded when the vesting is terminated
+    vesting_contract.terminate_vesting(&owner, U64(1)).await?;
+    assert!(vesting_contract.get_vesting_storage(U64(1)).await.is_none());
+    let balance = vesting_contract
+        .storage_balance_of(owner.id().clone())
+        .await
+        .unwrap();
+    assert_eq!(balance.total, balance_before.total);
+    assert_eq!(balance.available, balance_before.available);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_vesting_archive() -> anyhow::Result<()> {
+    let worker = workspaces::sandbox().await.unwrap();
+    let (vesting_contract, oct_contract, root, owner, beneficiary) = setup_vesting(&worker).await;
+
+    let block = worker.view_latest_block().await.unwrap();
+    let now = nano_to_seconds(block.timestamp());
+
+    for _ in 0..3 {
+        vesting_contract
+            .create_linear_vesting(
+                &owner,
+                beneficiary.id().clone(),
+                U64(now - 1440),
+                U64(now + 1440),
+                U128(100),
+            )
+            .await?;
+    }
+
+    // removed before the archive is enabled
+    vesting_contract.terminate_vesting(&owner, U64(1)).await?;
+    assert_eq!(vesting_contract.get_vesting_status(U64(1)).await, None);
+
+    vesting_contract.set_archive_enabled(&owner, true).await?;
+    vesting_contract.freeze_vesting(&owner, U64(3)).await?;
+    vesting_contract.terminate_vesting(&owner, U64(2)).await?;
+
+    assert_eq!(
+        vesting_contract.get_vesting_status(U64(2)).await,
+        Some(VestingStatus::Terminated)
+    );
+    assert_eq!(
+        vesting_contract.get_vesting_status(U64(3)).await,
+        Some(VestingStatus::Frozen)
+    );
+    let archived_vestings = vesting_contract
+        .get_archived_vestings(beneficiary.id().clone(), 0, 10)
+        .await;
+    assert_eq!(archived_vestings.len(), 1);
+    assert_eq!(archived_vestings[0].vesting_id, U64(2));
+    assert_eq!(archived_vestings[0].total_vesting_amount, 100);
+    assert_eq!(archived_vestings[0].status, VestingStatus::Terminated);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_vesting_stats() -> anyhow::Result<()> {
+    let worker = workspaces::sandbox().await.unwrap();
+    let (vesting_contract, oct_contract, root, owner, beneficiary) = setup_vesting(&worker).await;
+
+    let block = worker.view_latest_block().await.unwrap();
+    let now = nano_to_seconds(block.timestamp());
+
+    for _ in 0..3 {
+        vesting_contract
+            .create_linear_vesting(
+                &owner,
+                beneficiary.id().clone(),
+                U64(now - 1440),
+                U64(now + 1440),
+                U128(100),
+            )
+            .await?;
+    }
+    vesting_contract.freeze_vesting(&owner, U64(3)).await?;
+    vesting_contract.terminate_vesting(&owner, U64(2)).await?;
+
+    let stats = vesting_contract.get_stats().await;
+    assert_eq!(stats.active_count, 1);
+    assert_eq!(stats.frozen_count, 1);
+    assert_eq!(stats.finished_count, 0);
+    assert_eq!(stats.terminated_count, 1);
+    assert_eq!(stats.beneficiary_count, 1);
+    assert_eq!(stats.total_committed, 300);
+    assert_eq!(stats.total_claimed, 0);
+    assert_eq!(stats.total_clawed_back, 100);
+    assert!(stats.total_released > 0);
+
+    vesting_contract.terminate_vesting(&owner, U64(1)).await?;
+    vesting_contract.terminate_vesting(&owner, U64(3)).await?;
+    let stats = vesting_contract.get_stats().await;
+    assert_eq!(stats.active_count + stats.frozen_count, 0);
+    assert_eq!(stats.terminated_count, 3);
+    assert_eq!(stats.beneficiary_count, 0);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_filter_vestings() -> anyhow::Result<()> {
+    let worker = workspaces::sandbox().await.unwrap();
+    let (vesting_contract, oct_contract, root, owner, beneficiary) = setup_vesting(&worker).await;
+
+    let block = worker.view_latest_block().await.unwrap();
+    let now = nano_to_seconds(block.timestamp());
+
+    vesting_contract
+        .create_linear_vesting(
+            &owner,
+            beneficiary.id().clone(),
+            U64(now - 1440),
+            U64(now + 1440),
+            U128(100),
+        )
+        .await?;
+    vesting_contract
+        .create_cliff_vesting(
+            &owner,
+            beneficiary.id().clone(),
+            vec![CliffVestingCheckpoint {
+                time: now + 2880,
+                amount: 100,
+            }],
+        )
+        .await?;
+    vesting_contract.freeze_vesting(&owner, U64(2)).await?;
+
+    let vestings = vesting_contract
+        .get_vestings_by_ids(vec![U64(2), U64(99)])
+        .await;
+    assert_eq!(vestings.len(), 2);
+    assert_eq!(vestings[0].as_ref().unwrap().get_vesting_id(), U64(2));
+    assert!(vestings[1].is_none());
+
+    let vestings = vesting_contract
+        .get_filtered_vesting(
+            0,
+            10,
+            None,
+            VestingFilter {
+                vesting_type: Some(VestingType::Cliff),
+                is_frozen: Some(true),
+                ..Default::default()
+            },
+        )
+        .await;
+    assert_eq!(vestings.len(), 1);
+    assert_eq!(vestings[0].get_vesting_id(), U64(2));
+
+    let vestings = vesting_contract
+        .get_filtered_vesting(
+            0,
+            10,
+            Some(beneficiary.id().clone()),
+            VestingFilter {
+                max_end_time: Some(U64(now + 1440)),
+                ..Default::default()
+            },
+        )
+        .await;
+    assert_eq!(vestings.len(), 1);
+    assert_eq!(vestings[0].get_vesting_id(), U64(1));
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_vesting_view() -> anyhow::Result<()> {
+    let worker = workspaces::sandbox().await.unwrap();
+    let (vesting_contract, oct_contract, root, owner, beneficiary) = setup_vesting(&worker).await;
+
+    let block = worker.view_latest_block().await.unwrap();
+    let now = nano_to_seconds(block.timestamp());
+
+    vesting_contract
+        .create_cliff_vesting(
+            &owner,
+            beneficiary.id().clone(),
+            vec![
+                CliffVestingCheckpoint {
+                    time: now - 1440,
+                    amount: 10,
+                },
+                CliffVestingCheckpoint {
+                    time: now + 1440,
+                    amount: 20,
+                },
+            ],
+        )
+        .await?;
+
+    let view = vesting_contract.get_vesting_view(U64(1)).await.unwrap();
+    assert_eq!(view.vesting_type, VestingType::Cliff);
+    assert_eq!(view.beneficiary.as_str(), beneficiary.id().as_str());
+    assert_eq!(view.total_vesting_amount, 30);
+    assert_eq!(view.released_amount, 10);
+    assert_eq!(view.claimable_amount, 10);
+    assert_eq!(view.next_unlock_time, Some(U64(now + 1440)));
+    assert_eq!(view.next_unlock_amount, 20);
+    assert_eq!(view.finish_time, now + 1440);
+    assert_eq!(view.status, VestingStatus::Active);
+    assert!(vesting_contract.get_vesting_view(U64(2)).await.is_none());
+
+    let views = vesting_contract
+        .get_vesting_views(0, 10, Some(beneficiary.id().clone()))
+        .await;
+    assert_eq!(views, vec![view]);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_withdraw_with_unclaimed_airdrop() -> anyhow::Result<()> {
+    let worker = workspaces::sandbox().await.unwrap();
+    let (vesting_contract, oct_contract, root, owner, beneficiary) = setup_vesting(&worker).await;
+
+    vesting_contract
+        .register_merkle_airdrop(&owner, Base58CryptoHash::from([0u8; 32]), U128(30))
+        .await?;
+    oct_contract
+        .mint(vesting_contract.contract_id.clone(), U128(30))
+        .await?;
+
+    let withdraw = SensitiveAction::Withdraw {
+        receiver_id: owner.id().as_str().parse().unwrap(),
+        amount: U128(30),
+    };
+    vesting_contract
+        .schedule_action(&owner, withdraw.clone())
+        .await?;
+    let action_id = vesting_contract.get_scheduled_actions(0, 10).await[0].id;
+    let result = vesting_contract
+        .execute_scheduled_action(&owner, action_id)
+        .await;
+    assert!(result.unwrap_err().to_string().contains(
+        "Failed to withdraw because there are still airdrop vestings to be claimed in this contract."
+    ));
+
+    // the tokens can be withdrawn once the airdrop is removed
+    vesting_contract
+        .remove_merkle_airdrop(&owner, U64(1))
+        .await?;
+    let owner_balance = oct_contract.ft_balance_of(owner.id().clone()).await.0;
+    vesting_contract
+        .execute_scheduled_action(&owner, action_id)
+        .await?;
+    assert_eq!(
+        oct_contract.ft_balance_of(owner.id().clone()).await.0,
+        owner_balance + 30
+    );
+
+    Ok(())
+}
+
+async fn vesting_ids_of(
+    vesting_contract: &VestingContract<'_>,
+    account: &workspaces::Account,
+) -> Vec<U64> {
+    let mut vesting_ids = vesting_contract
+        .get_vesting(0, 10, Some(account.id().clone()))
+        .await
+        .iter()
+        .map(|e| e.get_vesting_id())
+        .collect::<Vec<_>>();
+    vesting_ids.sort_by_key(|e| e.0);
+    vesting_ids
+}
+
+#[tokio::test]
+async fn test_beneficiary_index() -> anyhow::Result<()> {
+    let worker = workspaces::sandbox().await.unwrap();
+    let (vesting_contract, oct_contract, root, owner, beneficiary) = setup_vesting(&worker).await;
+
+    let block = worker.view_latest_block().await.unwrap();
+    let now = nano_to_seconds(block.timestamp());
+
+    for _ in 0..2 {
+        vesting_contract
+            .create_linear_vesting(
+                &owner,
+                beneficiary.id().clone(),
+                U64(now - 1440),
+                U64(now + 1440),
+                U128(100),
+            )
+            .await?;
+    }
+    let new_beneficiary = register_account(&worker, &root, "new_beneficiary").await;
+    assert_eq!(
+        vesting_ids_of(&vesting_contract, &beneficiary).await,
+        vec![U64(1), U64(2)]
+    );
+    assert_eq!(vesting_contract.get_stats().await.beneficiary_count, 1);
+
+    // the index follows a beneficiary change
+    vesting_contract
+        .change_beneficiary(&beneficiary, U64(1), new_beneficiary.id().clone())
+        .await?;
+    assert_eq!(
+        vesting_ids_of(&vesting_contract, &beneficiary).await,
+        vec![U64(2)]
+    );
+    assert_eq!(
+        vesting_ids_of(&vesting_contract, &new_beneficiary).await,
+        vec![U64(1)]
+    );
+    assert_eq!(vesting_contract.get_stats().await.beneficiary_count, 2);
+
+    // and an amendment to another beneficiary
+    vesting_contract
+        .schedule_action(
+            &owner,
+            SensitiveAction::AmendVesting {
+                vesting_id: U64(2),
+                param: VestingCreateParam::LinearVesting {
+                    beneficiary: new_beneficiary.id().as_str().parse().unwrap(),
+                    start_time: now - 1440,
+                    end_time: now + 2880,
+                    total_vesting_amount: 100,
+                },
+            },
+        )
+        .await?;
+    let action_id = vesting_contract.get_scheduled_actions(0, 10).await[0].id;
+    vesting_contract
+        .execute_scheduled_action(&owner, action_id)
+        .await?;
+    assert!(vesting_ids_of(&vesting_contract, &beneficiary)
+        .await
+        .is_empty());
+    assert_eq!(
+        vesting_ids_of(&vesting_contract, &new_beneficiary).await,
+        vec![U64(1), U64(2)]
+    );
+    assert_eq!(vesting_contract.get_stats().await.beneficiary_count, 1);
+
+    // and the removal of a vesting
+    vesting_contract.terminate_vesting(&owner, U64(1)).await?;
+    assert_eq!(
+        vesting_ids_of(&vesting_contract, &new_beneficiary).await,
+        vec![U64(2)]
+    );
+    vesting_contract.terminate_vesting(&owner, U64(2)).await?;
+    assert!(vesting_ids_of(&vesting_contract, &new_beneficiary)
+        .await
+        .is_empty());
+    assert_eq!(vesting_contract.get_stats().await.beneficiary_count, 0);
+
+    Ok(())
+}
+
+/// The data of the user action events in the logs of a call.
+fn user_action_data(logs: Vec<&str>, user_action: &str) -> Vec<Value> {
+    logs.iter()
+        .filter_map(|e| e.strip_prefix("EVENT_JSON:"))
+        .map(|e| near_sdk::serde_json::from_str::<Value>(e).unwrap())
+        .filter(|e| e["user_action"] == user_action)
+        .map(|e| e["data"].clone())
+        .collect()
+}
+
+#[tokio::test]
+async fn test_create_vesting_from_template() -> anyhow::Result<()> {
+    let worker = workspaces::sandbox().await.unwrap();
+    let (vesting_contract, oct_contract, root, owner, beneficiary) = setup_vesting(&worker).await;
+
+    let block = worker.view_latest_block().await.unwrap();
+    let now = nano_to_seconds(block.timestamp());
+
+    vesting_contract
+        .set_vesting_template(
+            &owner,
+            "linear".to_string(),
+            VestingTemplate::LinearVesting { duration: 1000 },
+        )
+        .await?;
+    vesting_contract
+        .set_vesting_template(
+            &owner,
+            "cliff".to_string(),
+            VestingTemplate::CliffVesting {
+                checkpoints: vec![
+                    TemplateCheckpoint {
+                        offset: 0,
+                        ratio: 2500,
+                    },
+                    TemplateCheckpoint {
+                        offset: 500,
+                        ratio: 7500,
+                    },
+                ],
+            },
+        )
+        .await?;
+    let mut template_ids = vesting_contract
+        .get_vesting_templates(0, 10)
+        .await
+        .into_iter()
+        .map(|e| e.id)
+        .collect::<Vec<_>>();
+    template_ids.sort();
+    assert_eq!(
+        template_ids,
+        vec!["cliff".to_string(), "linear".to_string()]
+    );
+
+    let result = vesting_contract
+        .create_vesting_from_template(
+            &owner,
+            "linear".to_string(),
+            beneficiary.id().clone(),
+            U64(now),
+            U128(100),
+        )
+        .await?;
+    let data = user_action_data(result.logs(), "create_vesting_from_template");
+    assert_eq!(data.len(), 1);
+    assert_eq!(data[0]["template_id"], "linear");
+    assert_eq!(data[0]["vesting_id"], "1");
+    let linear_vesting = match vesting_contract.get_vesting_by_id(U64(1)).await {
+        Vesting::NaturalTimeLinearVesting(v) => v,
+        Vesting::TimeCliffVesting(_) => panic!("should be NaturalTimeLinearVesting"),
+    };
+    assert_eq!(linear_vesting.start_time, now);
+    assert_eq!(linear_vesting.end_time, now + 1000);
+    assert_eq!(linear_vesting.vesting_token_info.total_vesting_amount, 100);
+
+    // the rounding remainder is released at the last checkpoint
+    let result = vesting_contract
+        .create_vesting_from_template(
+            &owner,
+            "cliff".to_string(),
+            beneficiary.id().clone(),
+            U64(now),
+            U128(101),
+        )
+        .await?;
+    let data = user_action_data(result.logs(), "create_vesting_from_template");
+    assert_eq!(data.len(), 1);
+    assert_eq!(data[0]["template_id"], "cliff");
+    assert_eq!(data[0]["vesting_id"], "2");
+    let cliff_vesting = match vesting_contract.get_vesting_by_id(U64(2)).await {
+        Vesting::TimeCliffVesting(v) => v,
+        Vesting::NaturalTimeLinearVesting(_) => panic!("should be TimeCliffVesting"),
+    };
+    assert_eq!(
+        cliff_vesting.time_cliff_list,
+        vec![
+            CliffVestingCheckpoint {
+                time: now,
+                amount: 25,
+            },
+            CliffVestingCheckpoint {
+                time: now + 500,
+                amount: 76,
+            },
+        ]
+    );
+
+    let result = vesting_contract
+        .create_vesting_from_template(
+            &owner,
+            "unknown".to_string(),
+            beneficiary.id().clone(),
+            U64(now),
+            U128(100),
+        )
+        .await;
+    assert!(result
+        .unwrap_err()
+        .to_string()
+        .contains("No such vesting template: unknown."));
+
+    Ok(())
+}